@@ -0,0 +1,597 @@
+#![allow(clippy::module_inception)]
+pub mod flow {
+  #![allow(dead_code)]
+
+  pub use maxflow::{MaxFlow, Arc};
+  pub use mincostflow::{MinCostFlow, Status};
+  pub use gomory_hu::gomory_hu_tree;
+
+  pub mod maxflow {
+    use crate::graphs::graphs::{Graph, Edge as _, VertexId};
+    use rustc_hash::FxHashMap;
+    use num_traits::ToPrimitive;
+    use std::collections::VecDeque;
+
+    /// Dinic with capacity-scaling, `O(NM log U)`
+    /// arc `e` has its reverse arc at `e ^ 1`
+    #[derive(Debug, Clone, Default)]
+    pub struct MaxFlow {
+      graph: Vec<Vec<usize>>,
+      arcs: Vec<Arc>,
+    }
+    impl MaxFlow {
+      pub fn new() -> Self { Self { graph: vec![], arcs: vec![] } }
+
+      /// network whose capacities are the weights of `graph`
+      /// edge `e` of `graph` becomes arc `2 * e`; returns the vertex numbering too
+      pub fn from_graph<V: VertexId, E: ToPrimitive, G: Graph<V, E> + ?Sized>(graph: &G) -> (Self, FxHashMap<V, usize>) {
+        let mut network = Self::new();
+        let mut index = FxHashMap::default();
+        graph.each_vertex(|v| { index.entry(v).or_insert_with(|| network.add_vertex() ); });
+        for e in 0 .. graph.m() {
+          let edge = graph.edge(e);
+          let from = *index.entry(edge.from()).or_insert_with(|| network.add_vertex() );
+          let to = *index.entry(edge.to()).or_insert_with(|| network.add_vertex() );
+          network.add_arc(from, to, edge.weight().to_i64().unwrap());
+        }
+        (network, index)
+      }
+
+      pub fn n(&self) -> usize { self.graph.len() }
+
+      pub fn add_vertex(&mut self) -> usize {
+        let v = self.graph.len();
+        self.graph.push(vec![]);
+        v
+      }
+
+      pub fn add_vertices(&mut self, add: usize) -> Vec<usize> {
+        self.graph.reserve(add);
+        (0 .. add).map(|_| self.add_vertex() ).collect::<Vec<_>>()
+      }
+
+      pub fn add_arc(&mut self, from: usize, to: usize, cap: i64) -> usize {
+        assert!(from < self.graph.len());
+        assert!(to < self.graph.len());
+        assert!(cap >= 0);
+        let e = self.arcs.len();
+        self.arcs.push(Arc { from, to, cap, flow: 0, rev: e + 1 });
+        self.arcs.push(Arc { from: to, to: from, cap, flow: cap, rev: e });
+        self.graph[from].push(e);
+        self.graph[to].push(e + 1);
+        e
+      }
+
+      pub fn arc(&self, e: usize) -> &Arc {
+        assert!(e < self.arcs.len());
+        &self.arcs[e]
+      }
+
+      /// arcs added by `add_arc` (reverse arcs excluded)
+      pub fn arcs(&self) -> impl Iterator<Item = (usize, &Arc)> { self.arcs.iter().enumerate().step_by(2) }
+
+      pub fn residual_cap(&self, e: usize) -> i64 { self.arc(e).residual_cap() }
+
+      /// removes all flow
+      pub fn reset(&mut self) {
+        for (e, arc) in self.arcs.iter_mut().enumerate() {
+          arc.flow = if e % 2 == 0 { 0 } else { arc.cap };
+        }
+      }
+
+      pub fn flow(&mut self, source: usize, sink: usize) -> i64 {
+        let limit = self.graph[source].iter().fold(0i64, |sum, &e| sum.saturating_add(self.arcs[e].residual_cap()) );
+        self.flow_limited(source, sink, limit)
+      }
+
+      pub fn flow_limited(&mut self, source: usize, sink: usize, limit: i64) -> i64 {
+        assert!(source < self.graph.len());
+        assert!(sink < self.graph.len());
+        if source == sink { return 0 }
+        let mut flow_sum = 0;
+        let max_cap = self.arcs.iter().map(|arc| arc.residual_cap() ).max().unwrap_or(0);
+        let mut unit = (max_cap as u64).next_power_of_two() as i64;
+        let mut indices = vec![0; self.graph.len()];
+        while unit > 0 && flow_sum < limit {
+          loop {
+            let level = self.dual(source, unit);
+            if level[sink] == self.graph.len() {
+              break;
+            }
+            indices.iter_mut().for_each(|i| *i = 0 );
+            let flow = self.primal(source, sink, &level, &mut indices, unit, limit - flow_sum);
+            if flow == 0 { break }
+            flow_sum += flow;
+          }
+          unit >>= 1;
+        }
+        flow_sum
+      }
+
+      /// reachability from `source` in the residual network
+      /// after `flow(source, sink)`, vertices marked `true` form a minimum cut
+      pub fn cut(&self, source: usize) -> Vec<bool> {
+        self.dual(source, 1).into_iter().map(|l| l < self.graph.len() ).collect::<Vec<_>>()
+      }
+
+      /// vertices on the source side of the minimum cut (call `flow` first)
+      pub fn cut_vertices(&self, source: usize) -> Vec<usize> {
+        self.cut(source).into_iter().enumerate().filter(|&(_, c)| c ).map(|(v, _)| v ).collect()
+      }
+
+      /// arcs from the source side to the sink side of the minimum cut (call `flow` first)
+      pub fn cut_arcs(&self, source: usize) -> Vec<usize> {
+        let cut = self.cut(source);
+        self.arcs().filter(|&(_, arc)| cut[arc.from] && !cut[arc.to] ).map(|(e, _)| e ).collect()
+      }
+
+      /// decomposes the current flow from `source` to `sink` into paths `(amount, arcs)`
+      /// flow on cycles is ignored
+      pub fn decompose(&self, source: usize, sink: usize) -> Vec<(i64, Vec<usize>)> {
+        let n = self.graph.len();
+        let mut rest = self.arcs.iter().enumerate().map(|(e, arc)| if e % 2 == 0 { arc.flow } else { 0 } ).collect::<Vec<_>>();
+        let mut paths = vec![];
+        let mut indices = vec![0; n];
+        loop {
+          let mut path: Vec<usize> = vec![];
+          let mut pos = vec![None; n];
+          pos[source] = Some(0);
+          let mut u = source;
+          while u != sink {
+            while indices[u] < self.graph[u].len() && rest[self.graph[u][indices[u]]] == 0 { indices[u] += 1 }
+            let e = if let Some(&e) = self.graph[u].get(indices[u]) { e } else { break };
+            let v = self.arcs[e].to;
+            path.push(e);
+            if let Some(i) = pos[v] {
+              let cycle = path.split_off(i);
+              let delta = cycle.iter().map(|&e| rest[e] ).min().unwrap();
+              for &e in &cycle { rest[e] -= delta }
+              for &e in &cycle { pos[self.arcs[e].to] = None }
+              pos[v] = Some(i);
+            } else {
+              pos[v] = Some(path.len());
+            }
+            u = v;
+          }
+          if u != sink { break }
+          let delta = path.iter().map(|&e| rest[e] ).min().unwrap();
+          for &e in &path { rest[e] -= delta }
+          paths.push((delta, path));
+        }
+        paths
+      }
+
+      /// blocking flow along arcs with residual capacity `>= unit`, without recursion
+      fn primal(&mut self, source: usize, sink: usize, level: &[usize], indices: &mut [usize], unit: i64, limit: i64) -> i64 {
+        let mut flow_sum = 0;
+        let mut path: Vec<usize> = vec![];
+        let mut u = source;
+        while limit - flow_sum >= unit {
+          if u == sink {
+            let flow = path.iter().map(|&e| self.arcs[e].residual_cap() ).min().unwrap().min(limit - flow_sum);
+            for &e in &path {
+              let re = self.arcs[e].rev;
+              self.arcs[e].flow += flow;
+              self.arcs[re].flow -= flow;
+            }
+            flow_sum += flow;
+            // retreat to the first arc that is no longer usable
+            if let Some(i) = path.iter().position(|&e| self.arcs[e].residual_cap() < unit ) {
+              u = self.arcs[path[i]].from;
+              path.truncate(i);
+            }
+            continue;
+          }
+          while indices[u] < self.graph[u].len() {
+            let e = self.graph[u][indices[u]];
+            if level[self.arcs[e].to] == level[u] + 1 && self.arcs[e].residual_cap() >= unit { break }
+            indices[u] += 1;
+          }
+          if let Some(&e) = self.graph[u].get(indices[u]) {
+            path.push(e);
+            u = self.arcs[e].to;
+          } else if let Some(e) = path.pop() {
+            u = self.arcs[e].from;
+            indices[u] += 1;
+          } else {
+            break;
+          }
+        }
+        flow_sum
+      }
+
+      fn dual(&self, source: usize, unit: i64) -> Vec<usize> {
+        let mut level = vec![self.graph.len(); self.graph.len()];
+        level[source] = 0;
+        let mut queue = VecDeque::new();
+        queue.push_back(source);
+        while let Some(u) = queue.pop_front() {
+          for &e in &self.graph[u] {
+            let v = self.arcs[e].to;
+            if self.arcs[e].residual_cap() >= unit && level[v] > level[u] + 1 {
+              level[v] = level[u] + 1;
+              queue.push_back(v);
+            }
+          }
+        }
+        level
+      }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Arc {
+      pub from: usize, // from vertex id
+      pub to: usize, // to vertex id
+      pub cap: i64, // capacity
+      pub flow: i64, // flow
+      pub rev: usize, // reverse edge id
+    }
+    impl Arc {
+      pub fn residual_cap(&self) -> i64 {
+        self.cap - self.flow
+      }
+    }
+  }
+
+  /// Primal-Dual with capacity scaling, supporting negative costs and lower bounds
+  /// `O(m^2 log U log n)`
+  pub mod mincostflow {
+    use std::collections::*;
+    use std::cmp::*;
+    use std::ops::RangeBounds;
+
+    const INF_FLOW: i64 = 1_000_000_000_000_000_000;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Status {
+      Optimal,
+      Infeasible,
+    }
+    use Status::*;
+
+    #[derive(Default, Clone, Debug)]
+    pub struct MinCostFlow {
+      graph: Vec<Vec<usize>>,
+      arcs: Vec<Arc>,
+      excess: Vec<i64>,
+      potential: Vec<i64>,
+      dist: Vec<i64>,
+      prev: Vec<Option<usize>>,
+      supplies: Vec<usize>,
+      demands: Vec<usize>,
+    }
+    impl MinCostFlow {
+      pub fn new() -> Self { Self::default() }
+
+      pub fn n(&self) -> usize { self.graph.len() }
+
+      /// number of arcs including reverse arcs
+      pub fn m(&self) -> usize { self.arcs.len() }
+
+      pub fn add_vertex(&mut self) -> usize {
+        let v = self.n();
+        self.graph.push(vec![]);
+        self.excess.push(0);
+        v
+      }
+
+      pub fn add_vertices(&mut self, add: usize) -> Vec<usize> {
+        self.graph.reserve(add);
+        self.excess.reserve(add);
+        (0 .. add).map(|_| self.add_vertex() ).collect::<Vec<_>>()
+      }
+
+      pub fn add_arc(&mut self, from: usize, to: usize, cap: i64, cost: i64) -> usize {
+        assert!(cap >= 0);
+        self.add_constrained_arc(from, to, ..= cap, cost)
+      }
+
+      /// arc whose flow must be in `cap`
+      pub fn add_constrained_arc(&mut self, from: usize, to: usize, cap: impl RangeBounds<i64>, cost: i64) -> usize {
+        assert!(from < self.n());
+        assert!(to < self.n());
+        let (lower, upper) = parse_range(cap);
+        assert!(lower <= upper);
+        let e = self.m();
+        self.arcs.push(Arc { from, to, rev: e + 1, cap: upper, flow: 0, cost });
+        self.arcs.push(Arc { from: to, to: from, rev: e, cap: -lower, flow: 0, cost: -cost });
+        self.graph[from].push(e);
+        self.graph[to].push(e + 1);
+        e
+      }
+
+      pub fn arc(&self, e: usize) -> &Arc {
+        assert!(e < self.m());
+        &self.arcs[e]
+      }
+
+      pub fn add_supply(&mut self, v: usize, supply: i64) {
+        assert!(v < self.n());
+        self.excess[v] += supply;
+      }
+
+      pub fn add_demand(&mut self, v: usize, demand: i64) {
+        assert!(v < self.n());
+        self.excess[v] -= demand;
+      }
+
+      /// remaining supply (positive) or demand (negative) of `v`
+      pub fn excess(&self, v: usize) -> i64 { self.excess[v] }
+
+      /// dual potentials; after `solve` returns `Optimal`,
+      /// `cost + potential[from] - potential[to] >= 0` holds for every arc with positive residual capacity
+      pub fn potential(&self) -> &[i64] { &self.potential }
+
+      /// total cost of the current flow
+      pub fn cost(&self) -> i64 { self.arcs.iter().map(|arc| arc.flow * arc.cost ).sum::<i64>() / 2 }
+
+      pub fn max_flow(&mut self, source: usize, sink: usize) -> (i64, i64, Status) {
+        let limit = self.graph[source].iter().fold(self.excess[source].abs(), |sum, &e| sum.saturating_add(self.arcs[e].residual_cap().max(0)) ).min(INF_FLOW);
+        self.flow(source, sink, limit)
+      }
+
+      /// sends at most `limit` from `source` to `sink` in addition to the b-flow, returns `(flow, cost, status)`
+      pub fn flow(&mut self, source: usize, sink: usize, limit: i64) -> (i64, i64, Status) {
+        match self.slope(source, sink, limit) {
+          (_, Infeasible) => (0, self.cost(), Infeasible),
+          (slope, status) => {
+            let (flow, cost) = *slope.last().unwrap();
+            (flow, cost, status)
+          }
+        }
+      }
+
+      /// minimum cost b-flow satisfying all supplies, demands and lower bounds
+      /// returns `(cost, status)`; on `Infeasible` some excess remains
+      pub fn solve(&mut self) -> (i64, Status) {
+        self.potential.resize(self.n(), 0);
+        for e in 0 .. self.m() {
+          let rcap = self.arcs[e].residual_cap();
+          // saturate arcs of negative residual capacity (reverse arcs of lower bounds)
+          if rcap < 0 {
+            self.push(e, rcap);
+            self.excess[self.arcs[e].from] -= rcap;
+            self.excess[self.arcs[e].to] += rcap;
+          }
+        }
+
+        let mut scaling_unit = (self.arcs.iter().map(|arc| arc.residual_cap() ).max().unwrap_or(0).max(1) as u64).next_power_of_two() as i64;
+        while scaling_unit > 0 {
+          self.saturate_negative(scaling_unit);
+          while self.dual(scaling_unit) {
+            self.primal(scaling_unit);
+          }
+          scaling_unit /= 2;
+        }
+
+        if self.supplies.is_empty() && self.demands.is_empty() {
+          (self.cost(), Optimal)
+        } else {
+          (self.cost(), Infeasible)
+        }
+      }
+
+      /// after a feasible `solve`, sends at most `limit` more from `source` to `sink` by successive shortest paths
+      /// returns the breakpoints `(flow, cost)` of the minimum cost as a convex function of the additional flow,
+      /// starting at `(0, cost of the b-flow)`; the additional flow stays in the network
+      pub fn slope(&mut self, source: usize, sink: usize, limit: i64) -> (Vec<(i64, i64)>, Status) {
+        assert!(source < self.n());
+        assert!(sink < self.n());
+        assert!(source != sink);
+        let (mut cost, status) = self.solve();
+        if status == Infeasible { return (vec![], status) }
+        let n = self.n();
+        let mut flow = 0;
+        let mut result = vec![(0, cost)];
+        while flow < limit {
+          let mut dist = vec![INF_FLOW; n];
+          let mut prev = vec![None; n];
+          let mut pq = BinaryHeap::new();
+          dist[source] = 0;
+          pq.push((Reverse(0), source));
+          while let Some((Reverse(d), u)) = pq.pop() {
+            if dist[u] < d { continue }
+            for &e in &self.graph[u] {
+              let arc = self.arcs[e];
+              let d2 = d + self.reduced_cost(e);
+              if arc.residual_cap() > 0 && dist[arc.to] > d2 {
+                dist[arc.to] = d2;
+                prev[arc.to] = Some(e);
+                pq.push((Reverse(d2), arc.to));
+              }
+            }
+          }
+          if dist[sink] == INF_FLOW { break }
+          for v in 0 .. n {
+            self.potential[v] += dist[v].min(dist[sink]);
+          }
+          let mut delta = limit - flow;
+          let mut v = sink;
+          while let Some(e) = prev[v] {
+            delta = delta.min(self.arcs[e].residual_cap());
+            v = self.arcs[e].from;
+          }
+          let mut v = sink;
+          while let Some(e) = prev[v] {
+            self.push(e, delta);
+            v = self.arcs[e].from;
+          }
+          let unit_cost = self.potential[sink] - self.potential[source];
+          flow += delta;
+          cost += delta * unit_cost;
+          if result.len() >= 2 {
+            let (f1, c1) = result[result.len() - 2];
+            let (f2, c2) = result[result.len() - 1];
+            if (c2 - c1) == unit_cost * (f2 - f1) { result.pop(); }
+          }
+          result.push((flow, cost));
+        }
+        (result, Optimal)
+      }
+
+      fn dual(&mut self, min_flow: i64) -> bool {
+        self.dist.clear();
+        self.dist.resize(self.n(), INF_FLOW);
+        self.prev.clear();
+        self.prev.resize(self.n(), None);
+        let mut reachable_demands = 0;
+        let mut pq = BinaryHeap::new();
+        for &s in &self.supplies {
+          self.dist[s] = 0;
+          pq.push((Reverse(0), s));
+        }
+        let mut farthest = 0;
+        while let Some((Reverse(d), u)) = pq.pop() {
+          if self.dist[u] < d {
+            continue;
+          }
+          farthest = farthest.max(d);
+          if self.excess[u] <= -min_flow {
+            reachable_demands += 1;
+          }
+          if reachable_demands >= self.demands.len() {
+            break;
+          }
+          for &e in &self.graph[u] {
+            let arc = self.arcs[e];
+            let d2 = d.saturating_add(self.reduced_cost(e));
+            if arc.residual_cap() >= min_flow && self.dist[arc.to] > d2 {
+              self.dist[arc.to] = d2;
+              self.prev[arc.to] = Some(e);
+              pq.push((Reverse(d2), arc.to));
+            }
+          }
+        }
+        for v in 0 .. self.n() {
+          self.potential[v] = self.potential[v].saturating_add(self.dist[v].min(farthest));
+        }
+        reachable_demands > 0
+      }
+
+      fn primal(&mut self, min_flow: i64) {
+        for i in 0 .. self.demands.len() {
+          let t = self.demands[i];
+          if self.dist[t] == INF_FLOW {
+            continue;
+          }
+          let mut flow = -self.excess[t];
+          let mut v = t;
+          while let Some(e) = self.prev[v] {
+            flow = flow.min(self.arcs[e].residual_cap());
+            v = self.arcs[e].from;
+          }
+          flow = flow.min(self.excess[v]);
+          if flow < min_flow {
+            continue;
+          }
+          let mut v = t;
+          while let Some(e) = self.prev[v] {
+            self.push(e, flow);
+            if self.arcs[e].residual_cap() < min_flow {
+              self.prev[v] = None;
+            }
+            v = self.arcs[e].from;
+          }
+          self.excess[t] += flow;
+          self.excess[v] -= flow;
+        }
+        let excess = &mut self.excess;
+        self.supplies.retain(|&v| excess[v] >= min_flow );
+        self.demands.retain(|&v| excess[v] <= -min_flow );
+      }
+
+      fn saturate_negative(&mut self, min_flow: i64) {
+        for e in 0 .. self.m() {
+          let rcap = self.arcs[e].residual_cap();
+          let rcost = self.reduced_cost(e);
+          if rcost < 0 && rcap >= min_flow {
+            self.push(e, rcap);
+            self.excess[self.arcs[e].from] -= rcap;
+            self.excess[self.arcs[e].to] += rcap;
+          }
+        }
+        self.supplies.clear();
+        self.demands.clear();
+        for v in 0 .. self.n() {
+          if self.excess[v] >= min_flow {
+            self.supplies.push(v);
+          } else if self.excess[v] <= -min_flow {
+            self.demands.push(v);
+          }
+        }
+      }
+
+      fn push(&mut self, e: usize, amount: i64) {
+        let re = self.arcs[e].rev;
+        self.arcs[e].flow += amount;
+        self.arcs[re].flow -= amount;
+      }
+
+      fn reduced_cost(&self, e: usize) -> i64 {
+        let Arc { from, to, cost, .. } = self.arcs[e];
+        cost - (self.potential[to] - self.potential[from])
+      }
+    }
+
+    fn parse_range(range: impl RangeBounds<i64>) -> (i64, i64) {
+      use std::ops::Bound::*;
+      let lower = match range.start_bound() {
+        Included(&x) => x,
+        Excluded(&x) => x + 1,
+        Unbounded => 0,
+      };
+      let upper = match range.end_bound() {
+        Included(&x) => x,
+        Excluded(&x) => x - 1,
+        Unbounded => INF_FLOW,
+      };
+      (lower, upper)
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    pub struct Arc {
+      pub from: usize,
+      pub to: usize,
+      pub rev: usize,
+      pub cap: i64,
+      pub flow: i64,
+      pub cost: i64,
+    }
+    impl Arc {
+      pub fn residual_cap(&self) -> i64 {
+        self.cap - self.flow
+      }
+    }
+
+    // Ref: https://misawa.github.io/others/flow/library_design.html
+    // Ref: https://misawa.github.io/others/flow/lets_use_capacity_scaling.html
+  }
+
+  pub mod gomory_hu {
+    use super::MaxFlow;
+    use crate::graphs::graphs::{Graph, GraphMut, VecGraph, measure::Measure};
+    use num_traits::{ToPrimitive, NumCast};
+
+    /// Gomory-Hu tree (Gusfield) of an undirected graph on vertices `0 .. n`, by `n - 1` max flows
+    /// each undirected edge should be a pair of arcs (as `add_edge` does)
+    /// the minimum cut between `u` and `v` is the minimum weight on the tree path between them
+    pub fn gomory_hu_tree<C: Measure + ToPrimitive + NumCast, G: Graph<usize, C> + ?Sized>(graph: &G) -> VecGraph<C> {
+      let n = graph.n();
+      let (mut network, index) = MaxFlow::from_graph(graph);
+      let mut parent = vec![0; n];
+      let mut tree = VecGraph::new();
+      for v in 0 .. n { tree.vertex_mut(v); }
+      for s in 1 .. n {
+        let t = parent[s];
+        network.reset();
+        let cut_value = network.flow(index[&s], index[&t]);
+        let cut = network.cut(index[&s]);
+        for v in s + 1 .. n {
+          if cut[index[&v]] && parent[v] == t { parent[v] = s }
+        }
+        tree.add_edge(s, t, C::from(cut_value).unwrap());
+      }
+      tree
+    }
+  }
+}
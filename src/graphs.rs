@@ -5,6 +5,17 @@ pub mod graphs {
   pub use dic_graph::{DicGraph, VecGraph, HashGraph};
   pub use sub_graph::SubGraph;
   pub use grid_graph::GridGraph;
+  pub use two_sat::TwoSat;
+  pub use lowlink::Lowlink;
+  pub use bipartite::BipartiteMatching;
+  pub use hld::HeavyLightDecomposition;
+  pub use euler_tour::EulerTour;
+  pub use lca::{LCA, PathFold};
+  pub use functional::FunctionalGraph;
+  pub use dominator::DominatorTree;
+  pub use bitmask::{chromatic_number, maximum_independent_set, maximum_clique, shortest_hamiltonian_path, shortest_hamiltonian_path_by, shortest_hamiltonian_cycle, shortest_hamiltonian_cycle_by};
+  pub use centroid::CentroidDecomposition;
+  pub use rerooting::{rerooting, RerootingHelper};
 
   pub trait VertexId: Copy + Clone + Eq + std::hash::Hash {}
   impl<V: Copy + Clone + Eq + std::hash::Hash> VertexId for V {}
@@ -79,18 +90,24 @@ pub mod graphs {
       }
     }
 
+    /// do DFS and pass edges of the euler tour of the DFS tree
+    /// each tree edge is passed when descending, and its reverse edge (if exists) when returning
     fn eulertour(&self, from: V, mut f: impl FnMut(&Self::Edge)) {
-      let mut visited = vec![false; self.m()];
-      let mut stack = self.edges_from(from);
-      for &e in &stack { visited[e] = true }
-      while let Some(e) = stack.pop() {
-        (f)(self.edge(e));
-        self.each_edge_from(self.edge(e).to(), |d| {
-          if !visited[d] {
-            visited[d] = true;
-            stack.push(d);
+      let mut visited = FxHashSet::default();
+      visited.insert(from);
+      let mut stack = vec![(None, self.edges_from(from).into_iter())];
+      while let Some((via, edges)) = stack.last_mut() {
+        let via = *via;
+        if let Some(e) = edges.next() {
+          let v = self.edge(e).to();
+          if visited.insert(v) {
+            (f)(self.edge(e));
+            stack.push((Some(e), self.edges_from(v).into_iter()));
           }
-        });
+        } else {
+          stack.pop();
+          if let Some(r) = via.and_then(|e| self.reverse_edge(e) ) { (f)(self.edge(r)) }
+        }
       }
     }
     
@@ -109,7 +126,255 @@ pub mod graphs {
       });
       components
     }
-    
+
+    /// strongly connected components (Tarjan, non-recursive)
+    /// returns `(id, dag)`
+    /// `id[v]` is the component of `v`, numbered in topological order
+    /// `dag` has an arc `id[u] -> id[v]` for each pair of components joined by an arc `u -> v`
+    fn strongly_connected_components(&self) -> (FxHashMap<V, usize>, VecGraph) {
+      let indexed = IndexedGraph::new(self);
+      let n = indexed.vertices.len();
+      let mut order = vec![n; n];
+      let mut low = vec![0; n];
+      let mut comp = vec![n; n];
+      let mut stack = vec![];
+      let mut path: Vec<(usize, usize)> = vec![];
+      let mut time = 0;
+      let mut count = 0;
+      for s in 0 .. n {
+        if order[s] != n { continue }
+        order[s] = time;
+        low[s] = time;
+        time += 1;
+        stack.push(s);
+        path.push((s, 0));
+        while let Some(&(u, i)) = path.last() {
+          if let Some(&(_, v)) = indexed.adj[u].get(i) {
+            path.last_mut().unwrap().1 += 1;
+            if order[v] == n {
+              order[v] = time;
+              low[v] = time;
+              time += 1;
+              stack.push(v);
+              path.push((v, 0));
+            } else if comp[v] == n {
+              low[u] = low[u].min(order[v]);
+            }
+            continue;
+          }
+          path.pop();
+          if let Some(&(p, _)) = path.last() { low[p] = low[p].min(low[u]) }
+          if low[u] == order[u] {
+            while let Some(v) = stack.pop() {
+              comp[v] = count;
+              if v == u { break }
+            }
+            count += 1;
+          }
+        }
+      }
+      // Tarjan finds components in reverse topological order
+      for c in &mut comp { *c = count - 1 - *c }
+      let mut arcs = vec![];
+      for u in 0 .. n {
+        for &(_, v) in &indexed.adj[u] {
+          if comp[u] != comp[v] { arcs.push((comp[u], comp[v])) }
+        }
+      }
+      arcs.sort_unstable();
+      arcs.dedup();
+      let mut dag = VecGraph::new();
+      for c in 0 .. count { dag.vertex_mut(c); }
+      dag.add_arcs(arcs);
+      let id = indexed.vertices.iter().zip(comp).map(|(&v, c)| (v, c) ).collect::<FxHashMap<_, _>>();
+      (id, dag)
+    }
+
+    /// topological order by Kahn's algorithm
+    /// returns `None` if the graph has a cycle
+    fn topological_sort(&self) -> Option<Vec<V>> {
+      let indexed = IndexedGraph::new(self);
+      let order = indexed.topological_sort_indices()?;
+      Some(order.into_iter().map(|u| indexed.vertices[u] ).collect())
+    }
+
+    /// lexicographically smallest topological order
+    /// returns `None` if the graph has a cycle
+    fn topological_sort_min(&self) -> Option<Vec<V>> where V: Ord {
+      let indexed = IndexedGraph::new(self);
+      let vertices = &indexed.vertices;
+      let order = indexed.topological_order(|indeg| indeg.iter().enumerate().filter(|&(_, &d)| d == 0 ).map(|(u, _)| Reverse((vertices[u], u)) ).collect::<BinaryHeap<_>>(), |pq| pq.pop().map(|Reverse((_, u))| u ), |pq, v| pq.push(Reverse((vertices[v], v))) )?;
+      Some(order.into_iter().map(|u| vertices[u] ).collect())
+    }
+
+    /// longest path ending at each vertex in DAG
+    /// edges with `f(edge) == None` are ignored
+    /// returns `None` if the graph has a cycle
+    fn longest_path_dag_by<C: Measure>(&self, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> Option<FxHashMap<V, C>> {
+      let indexed = IndexedGraph::new(self);
+      let order = indexed.topological_sort_indices()?;
+      let mut dp = vec![C::zero(); indexed.vertices.len()];
+      for u in order {
+        for &(e, v) in &indexed.adj[u] {
+          if let Some(c) = (f)(self.edge(e)) { let d = dp[u] + c; dp[v].chmax(d); }
+        }
+      }
+      Some(indexed.vertices.into_iter().zip(dp).collect())
+    }
+
+    fn longest_path_dag(&self) -> Option<FxHashMap<V, E>> where E: Measure {
+      self.longest_path_dag_by(|edge| Some(*edge.weight()) )
+    }
+
+    /// number of paths from `from` to each reachable vertex in DAG
+    /// `one` is the count of the empty path (e.g. `ModInt::new(1)`)
+    /// returns `None` if the graph has a cycle
+    fn count_paths_dag<T: Copy + std::ops::Add<Output = T>>(&self, from: V, one: T) -> Option<FxHashMap<V, T>> {
+      let indexed = IndexedGraph::new(self);
+      let order = indexed.topological_sort_indices()?;
+      let mut count: Vec<Option<T>> = vec![None; indexed.vertices.len()];
+      count[indexed.index[&from]] = Some(one);
+      for u in order {
+        let c = if let Some(c) = count[u] { c } else { continue };
+        for &(_, v) in &indexed.adj[u] {
+          count[v] = Some(count[v].map_or(c, |d| d + c ));
+        }
+      }
+      Some(indexed.vertices.into_iter().zip(count).filter_map(|(v, c)| c.map(|c| (v, c) ) ).collect())
+    }
+
+    /// shortest distance from `from` and number of shortest paths to each reachable vertex in DAG
+    /// `one` is the count of the empty path (e.g. `ModInt::new(1)`)
+    /// returns `None` if the graph has a cycle
+    fn count_shortest_paths_dag_by<C: Measure, T: Copy + std::ops::Add<Output = T>>(&self, from: V, one: T, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> Option<FxHashMap<V, (C, T)>> {
+      let indexed = IndexedGraph::new(self);
+      let order = indexed.topological_sort_indices()?;
+      let mut dp: Vec<Option<(C, T)>> = vec![None; indexed.vertices.len()];
+      dp[indexed.index[&from]] = Some((C::zero(), one));
+      for u in order {
+        let (d, c) = if let Some(x) = dp[u] { x } else { continue };
+        for &(e, v) in &indexed.adj[u] {
+          let d = if let Some(w) = (f)(self.edge(e)) { d + w } else { continue };
+          dp[v] = match dp[v] {
+            Some((d2, c2)) if d2 < d => Some((d2, c2)),
+            Some((d2, c2)) if d2 == d => Some((d2, c2 + c)),
+            _ => Some((d, c)),
+          };
+        }
+      }
+      Some(indexed.vertices.into_iter().zip(dp).filter_map(|(v, x)| x.map(|x| (v, x) ) ).collect())
+    }
+
+    /// lowlink of undirected graph (bridges, articulation points, two-edge/biconnected components)
+    /// each undirected edge must be a pair of arcs linked by `reverse_edge` (as made by `GraphMut::add_edge`)
+    fn lowlink(&self) -> Lowlink<V> { Lowlink::new(self) }
+
+    /// edge ids of an eulerian trail (Hierholzer)
+    /// the trail is a circuit if possible; otherwise it starts at a vertex of odd degree (or out-degree > in-degree)
+    /// if `!directed`, arcs `u -> v` and `v -> u` are paired into one edge (as `add_edge` does) and the returned arcs are oriented along the trail
+    fn eulerian_trail(&self, directed: bool) -> Result<Vec<usize>, EulerianTrailError<V>> {
+      let indexed = IndexedGraph::new(self);
+      let n = indexed.vertices.len();
+      let adj = &indexed.adj;
+      // pair[e] is the id of the edge that arc `e` belongs to
+      let mut pair = vec![0; self.m()];
+      let mut k = 0;
+      if directed {
+        for &(e, _) in adj.iter().flatten() { pair[e] = k; k += 1 }
+      } else {
+        let mut arcs: FxHashMap<(usize, usize), (Vec<usize>, Vec<usize>)> = FxHashMap::default();
+        for (u, list) in adj.iter().enumerate() {
+          for &(e, v) in list {
+            let entry = arcs.entry((u.min(v), u.max(v))).or_default();
+            if u <= v { entry.0.push(e) } else { entry.1.push(e) }
+          }
+        }
+        for ((u, v), (mut forward, mut backward)) in arcs {
+          if u == v {
+            // `add_edge(u, u, _)` pushes two consecutive arcs
+            forward.sort();
+            backward = forward.iter().skip(1).step_by(2).copied().collect();
+            forward = forward.into_iter().step_by(2).collect();
+          }
+          if forward.len() != backward.len() {
+            let e = if forward.len() > backward.len() { forward[backward.len()] } else { backward[forward.len()] };
+            return Err(EulerianTrailError::Unpaired(e));
+          }
+          for (a, b) in forward.into_iter().zip(backward) {
+            pair[a] = k;
+            pair[b] = k;
+            k += 1;
+          }
+        }
+      }
+      if k == 0 { return Ok(vec![]) }
+
+      let mut balance = adj.iter().map(|list| list.len() as isize ).collect::<Vec<_>>();
+      let mut start = (0 .. n).find(|&u| !adj[u].is_empty() ).unwrap();
+      if directed {
+        for &(_, v) in adj.iter().flatten() { balance[v] -= 1 }
+        let unbalanced = (0 .. n).filter(|&u| balance[u] != 0 ).collect::<Vec<_>>();
+        match unbalanced.len() {
+          0 => {},
+          2 if balance[unbalanced[0]] * balance[unbalanced[1]] == -1 => {
+            start = if balance[unbalanced[0]] == 1 { unbalanced[0] } else { unbalanced[1] };
+          },
+          _ => return Err(EulerianTrailError::Unbalanced(unbalanced.into_iter().map(|u| (indexed.vertices[u], balance[u]) ).collect())),
+        }
+      } else {
+        let odd = (0 .. n).filter(|&u| balance[u] % 2 != 0 ).collect::<Vec<_>>();
+        match odd.len() {
+          0 => {},
+          2 => start = odd[0],
+          _ => return Err(EulerianTrailError::OddDegree(odd.into_iter().map(|u| indexed.vertices[u] ).collect())),
+        }
+      }
+
+      let mut used = vec![false; k];
+      let mut next = vec![0; n];
+      let mut stack = vec![(start, None)];
+      let mut trail = Vec::with_capacity(k);
+      while let Some(&(u, via)) = stack.last() {
+        while next[u] < adj[u].len() && used[pair[adj[u][next[u]].0]] { next[u] += 1 }
+        if let Some(&(e, v)) = adj[u].get(next[u]) {
+          used[pair[e]] = true;
+          stack.push((v, Some(e)));
+        } else {
+          stack.pop();
+          trail.extend(via);
+        }
+      }
+      if trail.len() < k { return Err(EulerianTrailError::Disconnected) }
+      trail.reverse();
+      Ok(trail)
+    }
+
+    /// 2-coloring (`false`/`true`), or edge ids of an odd cycle
+    /// arcs are regarded as undirected edges (the cycle may use an arc against its direction)
+    fn is_bipartite(&self) -> Result<FxHashMap<V, bool>, Vec<usize>> {
+      let indexed = IndexedGraph::new(self);
+      let color = bipartite::coloring(&indexed.undirected())?;
+      Ok(indexed.vertices.into_iter().zip(color).collect())
+    }
+
+    /// maximum matching of bipartite graph (Hopcroft-Karp) in `O(m sqrt(n))`
+    /// sides are decided by `is_bipartite`; returns the odd cycle if not bipartite
+    fn bipartite_matching(&self) -> Result<BipartiteMatching<V>, Vec<usize>> { BipartiteMatching::new(self) }
+
+    /// maximum cardinality matching of general graph (Edmonds' blossom) in `O(n^3)`
+    /// arcs are regarded as undirected edges; returns edge ids of the matching
+    fn maximum_matching(&self) -> Vec<usize> {
+      let indexed = IndexedGraph::new(self);
+      general_matching::blossom(&indexed.undirected())
+    }
+
+    /// size of maximum matching via rank of random Tutte matrix over `ModInt` in `O(n^3)`
+    /// correct with probability at least `1 - n / 998244353`
+    fn maximum_matching_size_tutte(&self) -> usize {
+      let indexed = IndexedGraph::new(self);
+      general_matching::tutte_rank(&indexed.adj) / 2
+    }
+
     /// do BFS
     /// edge is passed to `f`
     /// Note that `from` vertex is not passed to `f`
@@ -124,110 +389,522 @@ pub mod graphs {
       dist
     }
 
-    fn shortest_path_bfs_by<C: Measure>(&self, from: V, mut f: impl FnMut(&Self::Edge, C) -> Option<C>) -> FxHashMap<V, C> {
-      let mut dist = FxHashMap::default();
-      dist.insert(from, C::zero());
+    fn shortest_path_bfs_by<C: Measure>(&self, from: V, f: impl FnMut(&Self::Edge, C) -> Option<C>) -> FxHashMap<V, C> {
+      self.shortest_path_tree_bfs_by(from, f).into_dist()
+    }
+
+    /// same as `shortest_path_bfs_by` but also records the edge used to reach each vertex
+    fn shortest_path_tree_bfs_by<C: Measure>(&self, from: V, mut f: impl FnMut(&Self::Edge, C) -> Option<C>) -> ShortestPathTree<V, C> {
+      let mut tree = ShortestPathTree::new(from);
       self.walk(from, |walker, u| self.each_edge_from(u, |e| {
-        if let Some(d) = (f)(self.edge(e), dist[&u]) {
+        if let Some(d) = (f)(self.edge(e), tree.dist[&u]) {
           if walker.go_later(self.edge(e).to()) {
-            dist.insert(self.edge(e).to(), d);
+            tree.set(self.edge(e).to(), d, e, u);
           }
         }
       }));
-      dist
+      tree
+    }
+
+    fn shortest_path_tree_bfs<T: Measure>(&self, from: V) -> ShortestPathTree<V, T> {
+      self.shortest_path_tree_bfs_by(from, |_, d| Some(d + T::one()) )
     }
 
     /// `f(e, d)? >= d`
     ///FIXME: なんかこわれてるので直す
-    fn shortest_path_dijkstra_by<C: Measure>(&self, from: V, mut f: impl FnMut(&Self::Edge, C) -> Option<C>) -> FxHashMap<V, C> {
-      let mut dist = FxHashMap::default();
-      dist.insert(from, C::zero());
+    fn shortest_path_dijkstra_by<C: Measure>(&self, from: V, f: impl FnMut(&Self::Edge, C) -> Option<C>) -> FxHashMap<V, C> {
+      self.shortest_path_tree_dijkstra_by(from, f).into_dist()
+    }
+
+    fn shortest_path_dijkstra(&self, from: V) -> FxHashMap<V, E> where E: Measure {
+      self.shortest_path_dijkstra_by(from, |edge, d| Some(d + *edge.weight()) )
+    }
+
+    /// same as `shortest_path_dijkstra_by` but also records the edge used to reach each vertex
+    fn shortest_path_tree_dijkstra_by<C: Measure>(&self, from: V, mut f: impl FnMut(&Self::Edge, C) -> Option<C>) -> ShortestPathTree<V, C> {
+      let mut tree = ShortestPathTree::new(from);
       let mut pq = BinaryHeap::new();
       pq.push(DistV(C::zero(), from));
       while let Some(DistV(d1, u)) = pq.pop() {
-        if dist[&u] != d1 { continue }
+        if tree.dist[&u] != d1 { continue }
         self.each_edge_from(u, |e| if let Some(d) = (f)(self.edge(e), d1) {
-          dist.if_chmin(self.edge(e).to(), d, || pq.push(DistV(d, self.edge(e).to())) );
+          let v = self.edge(e).to();
+          if tree.relax(v, d, e, u) { pq.push(DistV(d, v)) }
         } );
       }
-      dist
+      tree
     }
 
-    fn shortest_path_dijkstra(&self, from: V) -> FxHashMap<V, E> where E: Measure {
-      self.shortest_path_dijkstra_by(from, |edge, d| Some(d + *edge.weight()) )
+    fn shortest_path_tree_dijkstra(&self, from: V) -> ShortestPathTree<V, E> where E: Measure {
+      self.shortest_path_tree_dijkstra_by(from, |edge, d| Some(d + *edge.weight()) )
     }
 
-    fn shortest_path_spfa_by<C: Measure>(&self, from: V, mut f: impl FnMut(&Self::Edge, C) -> Option<C>) -> FxHashMap<V, C> {
-      let mut dist = FxHashMap::default();
-      dist.insert(from, C::zero());
+    /// 0-1 BFS
+    /// `f(e, d)?` must be `d` or `d + 1`
+    fn shortest_path_01bfs_by<C: Measure>(&self, from: V, f: impl FnMut(&Self::Edge, C) -> Option<C>) -> FxHashMap<V, C> {
+      self.shortest_path_tree_01bfs_by(from, f).into_dist()
+    }
+
+    fn shortest_path_01bfs(&self, from: V) -> FxHashMap<V, E> where E: Measure {
+      self.shortest_path_01bfs_by(from, |edge, d| Some(d + *edge.weight()) )
+    }
+
+    /// same as `shortest_path_01bfs_by` but also records the edge used to reach each vertex
+    fn shortest_path_tree_01bfs_by<C: Measure>(&self, from: V, mut f: impl FnMut(&Self::Edge, C) -> Option<C>) -> ShortestPathTree<V, C> {
+      let mut tree = ShortestPathTree::new(from);
+      let mut done = FxHashSet::default();
+      self.walk(from, |walker, u| {
+        if !done.insert(u) { return }
+        let d1 = tree.dist[&u];
+        self.each_edge_from(u, |e| if let Some(d) = (f)(self.edge(e), d1) {
+          let v = self.edge(e).to();
+          if tree.relax(v, d, e, u) {
+            walker.forget(v);
+            if d == d1 { walker.go_next(v) } else { walker.go_later(v) };
+          }
+        } );
+      });
+      tree
+    }
+
+    /// Dial's algorithm (Dijkstra with bucket queue) in `O(n max_weight + m)`
+    /// `f(e, d)? - d` must be in `0 ..= max_weight`
+    fn shortest_path_dial_by<C: Measure + ToPrimitive>(&self, from: V, max_weight: usize, f: impl FnMut(&Self::Edge, C) -> Option<C>) -> FxHashMap<V, C> {
+      self.shortest_path_tree_dial_by(from, max_weight, f).into_dist()
+    }
+
+    fn shortest_path_dial(&self, from: V, max_weight: usize) -> FxHashMap<V, E> where E: Measure + ToPrimitive {
+      self.shortest_path_dial_by(from, max_weight, |edge, d| Some(d + *edge.weight()) )
+    }
+
+    /// same as `shortest_path_dial_by` but also records the edge used to reach each vertex
+    fn shortest_path_tree_dial_by<C: Measure + ToPrimitive>(&self, from: V, max_weight: usize, mut f: impl FnMut(&Self::Edge, C) -> Option<C>) -> ShortestPathTree<V, C> {
+      let mut tree = ShortestPathTree::new(from);
+      let k = max_weight + 1;
+      let mut buckets = vec![vec![]; k];
+      buckets[0].push(from);
+      let mut pending = 1;
+      let mut d1 = C::zero();
+      while pending > 0 {
+        let b = d1.to_usize().unwrap() % k;
+        while let Some(u) = buckets[b].pop() {
+          pending -= 1;
+          if tree.dist[&u] != d1 { continue }
+          self.each_edge_from(u, |e| if let Some(d) = (f)(self.edge(e), d1) {
+            let v = self.edge(e).to();
+            if tree.relax(v, d, e, u) {
+              buckets[d.to_usize().unwrap() % k].push(v);
+              pending += 1;
+            }
+          } );
+        }
+        d1 += C::one();
+      }
+      tree
+    }
+
+    /// returns `Err` if a negative cycle is reachable from `from`
+    fn shortest_path_spfa_by<C: Measure>(&self, from: V, f: impl FnMut(&Self::Edge, C) -> Option<C>) -> Result<FxHashMap<V, C>, NegativeCycle<V, C>> {
+      self.shortest_path_tree_spfa_by(from, f).map(ShortestPathTree::into_dist)
+    }
+
+    fn shortest_path_spfa(&self, from: V) -> Result<FxHashMap<V, E>, NegativeCycle<V, E>> where E: Measure {
+      self.shortest_path_spfa_by(from, |edge, d| Some(d + *edge.weight()) )
+    }
+
+    /// same as `shortest_path_spfa_by` but also records the edge used to reach each vertex
+    /// when some path grows to `n` edges, falls back to `shortest_path_tree_bellman_ford_by` to report the negative cycle
+    fn shortest_path_tree_spfa_by<C: Measure>(&self, from: V, mut f: impl FnMut(&Self::Edge, C) -> Option<C>) -> Result<ShortestPathTree<V, C>, NegativeCycle<V, C>> {
+      // arc targets that were never registered count as vertices too
+      let n = IndexedGraph::new(self).len();
+      let mut tree = ShortestPathTree::new(from);
+      let mut len = FxHashMap::default();
+      len.insert(from, 0);
       let mut q = Uniqueue::new();
       q.push_front(from);
       while let Some(u) = q.pop_back() {
+        let mut negative = false;
         self.each_edge_from(u, |e| {
           let v = self.edge(e).to();
-          if let Some(d) = (f)(self.edge(e), dist[&u]) {
-            dist.if_chmin(v, d, || q.push_front(v) );
+          if let Some(d) = (f)(self.edge(e), tree.dist[&u]) {
+            if tree.relax(v, d, e, u) {
+              let l = len[&u] + 1;
+              len.insert(v, l);
+              negative |= l >= n;
+              q.push_front(v);
+            }
           }
         });
+        if negative { return self.shortest_path_tree_bellman_ford_by(from, f) }
       }
-      dist
+      Ok(tree)
     }
 
-    fn shortest_path_spfa(&self, from: V) -> FxHashMap<V, E> where E: Measure {
-      self.shortest_path_spfa_by(from, |edge, d| Some(d + *edge.weight()) )
+    fn shortest_path_tree_spfa(&self, from: V) -> Result<ShortestPathTree<V, E>, NegativeCycle<V, E>> where E: Measure {
+      self.shortest_path_tree_spfa_by(from, |edge, d| Some(d + *edge.weight()) )
     }
 
-    fn shortest_paths_floyd_warshall_by<C: Measure>(&self, loops: bool, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> FxHashMap<V, FxHashMap<V, C>> {
-      let mut dist = FxHashMap::default();
-      for e in 0 .. self.m() {
-        let edge = self.edge(e);
-        if let Some(d) = (f)(edge) { dist.entry(edge.from()).or_insert_with(FxHashMap::default).insert(edge.to(), d); }
+    /// Bellman-Ford in `O(nm)`
+    /// returns `Err` if a negative cycle is reachable from `from`
+    fn shortest_path_tree_bellman_ford_by<C: Measure>(&self, from: V, mut f: impl FnMut(&Self::Edge, C) -> Option<C>) -> Result<ShortestPathTree<V, C>, NegativeCycle<V, C>> {
+      let indexed = IndexedGraph::new(self);
+      let mut tree = ShortestPathTree::new(from);
+      let mut updated = vec![];
+      for _ in 0 .. indexed.len() {
+        updated.clear();
+        for (&u, adj) in indexed.vertices.iter().zip(&indexed.adj) {
+          for &(e, _) in adj {
+            let edge = self.edge(e);
+            if let Some(d) = tree.dist.get(&u).and_then(|&d| (f)(edge, d) ) {
+              if tree.relax(edge.to(), d, e, u) { updated.push(edge.to()) }
+            }
+          }
+        }
+        if updated.is_empty() { return Ok(tree) }
       }
-      if loops {
-        self.each_vertex(|v| {
-          dist.entry(v).or_insert_with(FxHashMap::default).insert(v, C::zero());
-        });
+      // a vertex relaxed in the `n`-th round is reached by a walk containing a negative cycle
+      // spread over the same edges as the relaxation, i.e. those `f` accepts
+      let mut unbounded = FxHashSet::default();
+      let mut cycle = vec![];
+      let mut stack = vec![];
+      for &v in &updated {
+        if unbounded.insert(v) { stack.push(v) }
       }
-      self.each_vertex(|k| {
-        self.each_vertex(|i| {
-          self.each_vertex(|j| {
-            if let Some((&d1, &d2)) = self::measure::zip(dist.get(&i).and_then(|d| d.get(&k) ), dist.get(&k).and_then(|d| d.get(&j) )) {
-              dist.entry(i).or_insert_with(FxHashMap::default).chmin(j, d1 + d2);
-            }
-          });
-        });
-      });
-      dist
+      while let Some(u) = stack.pop() {
+        for &(e, _) in &indexed.adj[indexed.index[&u]] {
+          let edge = self.edge(e);
+          if tree.dist.get(&u).and_then(|&d| (f)(edge, d) ).is_some() && unbounded.insert(edge.to()) { stack.push(edge.to()) }
+        }
+      }
+      for &v in &updated {
+        let mut visited = FxHashSet::default();
+        let mut x = v;
+        while visited.insert(x) {
+          match tree.prev.get(&x) {
+            Some(&(_, u)) => x = u,
+            None => break,
+          }
+        }
+        if !tree.prev.contains_key(&x) { continue }
+        let start = x;
+        loop {
+          let (e, u) = tree.prev[&x];
+          cycle.push(e);
+          x = u;
+          if x == start { break }
+        }
+        cycle.reverse();
+        break;
+      }
+      let mut dist = tree.into_dist();
+      dist.retain(|v, _| !unbounded.contains(v) );
+      Err(NegativeCycle { cycle, unbounded, dist })
+    }
+
+    fn shortest_path_tree_bellman_ford(&self, from: V) -> Result<ShortestPathTree<V, E>, NegativeCycle<V, E>> where E: Measure {
+      self.shortest_path_tree_bellman_ford_by(from, |edge, d| Some(d + *edge.weight()) )
+    }
+
+    /// `VecGraph` uses `shortest_paths_floyd_warshall_dense_by` internally
+    fn shortest_paths_floyd_warshall_by<C: Measure>(&self, loops: bool, f: impl FnMut(&Self::Edge) -> Option<C>) -> FxHashMap<V, FxHashMap<V, C>> {
+      floyd_warshall_nested(self, loops, f)
     }
 
     fn shortest_paths_floyd_warshall(&self) -> FxHashMap<V, FxHashMap<V, E>> where E: Measure {
       self.shortest_paths_floyd_warshall_by(true, |edge| Some(*edge.weight()) )
     }
 
-    // fn minimum_spanning_tree_prim_by<C: Measure, F: FnMut(&Self::Edge) -> Option<C>>(&self, root: usize, mut f: F) -> (C, SubGraph<'_, V, E, Self>) {
-    //   let mut cost = C::zero();
-    //   let mut included = vec![false; self.n()];
-    //   let mut vertices = vec![root];
-    //   let mut edges = Vec::new();
-    //   let mut pq = self.edges_from(root).into_iter().filter_map(|e| (f)(self.edge(e)).map(|c| (Reverse(c), e) ) ).collect::<BinaryHeap<_>>();
-    //   included[root] = true;
-    //   while let Some((Reverse(c), e)) = pq.pop() {
-    //     let to = self.edge(e).to();
-    //     if included[to] { continue }
-    //     included[to] = true;
-    //     vertices.push(to);
-    //     edges.push(e);
-    //     cost += c;
-    //     for x in self.edges_from(to).into_iter().filter_map(|e| (f)(self.edge(e)).map(|c| (Reverse(c), e) ) ) {
-    //       pq.push(x);
-    //     }
-    //   }
-    //   (cost, SubGraph::new(self, vertices, edges))
-    // }
-
-    // fn minimum_spanning_tree_prim(&self, root: usize) -> (E, SubGraph<'_, V, E, Self>) where E: Measure {
-    //   self.minimum_spanning_tree_prim_by(root, |edge| Some(*edge.weight()) )
-    // }
+    /// Floyd-Warshall on a flat `n * n` matrix
+    /// if `loops` is false, `dist(v, v)` is the shortest cycle through `v`
+    /// returns `Err` (reported by Bellman-Ford) if the graph has a negative cycle
+    fn shortest_paths_floyd_warshall_dense_by<C: Measure>(&self, loops: bool, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> Result<DistanceMatrix<V, C>, NegativeCycle<V, C>> {
+      let indexed = IndexedGraph::new(self);
+      let n = indexed.vertices.len();
+      let mut dist: Vec<Option<C>> = vec![None; n * n];
+      let mut prev = vec![None; n * n];
+      if loops {
+        for i in 0 .. n { dist[i * n + i] = Some(C::zero()) }
+      }
+      for (i, adj) in indexed.adj.iter().enumerate() {
+        for &(e, j) in adj {
+          if let Some(c) = (f)(self.edge(e)) { dist[i * n + j].if_chmin(c, || prev[i * n + j] = Some((e, i)) ); }
+        }
+      }
+      let mut negative = None;
+      for k in 0 .. n {
+        for i in 0 .. n {
+          let d1 = if let Some(d) = dist[i * n + k] { d } else { continue };
+          for j in 0 .. n {
+            if let Some(d2) = dist[k * n + j] {
+              dist[i * n + j].if_chmin(d1 + d2, || prev[i * n + j] = prev[k * n + j] );
+            }
+          }
+        }
+        negative = (0 .. n).find(|&i| dist[i * n + i].map(|d| d < C::zero() ).unwrap_or(false) );
+        if negative.is_some() { break }
+      }
+      if let Some(i) = negative {
+        let err = self.shortest_path_tree_bellman_ford_by(indexed.vertices[i], |edge, d| (f)(edge).map(|c| d + c ) ).err();
+        return Err(err.expect("negative cycle"));
+      }
+      Ok(DistanceMatrix { vertices: indexed.vertices, index: indexed.index, dist, prev, potential: None })
+    }
+
+    fn shortest_paths_floyd_warshall_dense(&self) -> Result<DistanceMatrix<V, E>, NegativeCycle<V, E>> where E: Measure {
+      self.shortest_paths_floyd_warshall_dense_by(true, |edge| Some(*edge.weight()) )
+    }
+
+    /// Johnson's algorithm in `O(nm log n)`
+    /// potentials by SPFA from a virtual source connected to every vertex, then Dijkstra from every vertex on reduced costs
+    /// returns `Err` (reported by Bellman-Ford) if the graph has a negative cycle
+    fn shortest_paths_johnson_by<C: Measure>(&self, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> Result<DistanceMatrix<V, C>, NegativeCycle<V, C>> {
+      let indexed = IndexedGraph::new(self);
+      let n = indexed.vertices.len();
+      let adj = indexed.adj.iter().map(|list| list.iter().filter_map(|&(e, v)| (f)(self.edge(e)).map(|c| (e, v, c) ) ).collect::<Vec<_>>() ).collect::<Vec<_>>();
+      let mut potential = vec![C::zero(); n];
+      let mut len = vec![0; n];
+      let mut origin = (0 .. n).collect::<Vec<_>>();
+      let mut queue = (0 .. n).collect::<VecDeque<_>>();
+      let mut in_queue = vec![true; n];
+      while let Some(u) = queue.pop_front() {
+        in_queue[u] = false;
+        for &(_, v, c) in &adj[u] {
+          let p = potential[u] + c;
+          if p >= potential[v] { continue }
+          potential[v] = p;
+          len[v] = len[u] + 1;
+          origin[v] = origin[u];
+          if len[v] >= n {
+            // the walk from `origin[v]` to `v` contains a negative cycle
+            let err = self.shortest_path_tree_bellman_ford_by(indexed.vertices[origin[v]], |edge, d| (f)(edge).map(|c| d + c ) ).err();
+            return Err(err.expect("negative cycle"));
+          }
+          if !in_queue[v] {
+            in_queue[v] = true;
+            queue.push_back(v);
+          }
+        }
+      }
+      let mut dist = vec![None; n * n];
+      let mut prev = vec![None; n * n];
+      for (s, (row, prev_row)) in dist.chunks_mut(n.max(1)).zip(prev.chunks_mut(n.max(1))).enumerate() {
+        row[s] = Some(C::zero());
+        let mut pq = BinaryHeap::new();
+        pq.push(Reverse((C::zero(), s)));
+        while let Some(Reverse((d, u))) = pq.pop() {
+          if row[u] != Some(d) { continue }
+          for &(e, v, c) in &adj[u] {
+            let d = d + c + potential[u] - potential[v];
+            row[v].if_chmin(d, || { prev_row[v] = Some((e, u)); pq.push(Reverse((d, v))); });
+          }
+        }
+        for (v, x) in row.iter_mut().enumerate() {
+          if let Some(d) = x.as_mut() { *d = *d - potential[s] + potential[v] }
+        }
+      }
+      Ok(DistanceMatrix { vertices: indexed.vertices, index: indexed.index, dist, prev, potential: Some(potential) })
+    }
+
+    fn shortest_paths_johnson(&self) -> Result<DistanceMatrix<V, E>, NegativeCycle<V, E>> where E: Measure {
+      self.shortest_paths_johnson_by(|edge| Some(*edge.weight()) )
+    }
+
+    /// minimum spanning tree of the component containing `root`
+    /// edges with `f(edge) == None` are ignored
+    /// each chosen arc is included in the sub graph together with its reverse arc (if any)
+    fn minimum_spanning_tree_prim_by<C: Measure>(&self, root: V, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> (C, SubGraph<'_, V, E, Self>) where Self: Sized {
+      let mut cost = C::zero();
+      let mut included = FxHashSet::default();
+      let mut vertices = vec![root];
+      let mut edges = Vec::new();
+      let mut pq = BinaryHeap::new();
+      included.insert(root);
+      self.each_edge_from(root, |e| if let Some(c) = (f)(self.edge(e)) { pq.push((Reverse(c), e)) } );
+      while let Some((Reverse(c), e)) = pq.pop() {
+        let to = self.edge(e).to();
+        if !included.insert(to) { continue }
+        vertices.push(to);
+        edges.push(e);
+        edges.extend(self.reverse_edge(e));
+        cost += c;
+        self.each_edge_from(to, |e| if let Some(c) = (f)(self.edge(e)) {
+          if !included.contains(&self.edge(e).to()) { pq.push((Reverse(c), e)) }
+        } );
+      }
+      (cost, SubGraph::new(self, vertices, edges))
+    }
+
+    fn minimum_spanning_tree_prim(&self, root: V) -> (E, SubGraph<'_, V, E, Self>) where Self: Sized, E: Measure {
+      self.minimum_spanning_tree_prim_by(root, |edge| Some(*edge.weight()) )
+    }
+
+    /// minimum spanning forest (Kruskal)
+    /// edges with `f(edge) == None` are ignored
+    /// each chosen arc is included in the sub graph together with its reverse arc (if any)
+    fn minimum_spanning_tree_kruskal_by<C: Measure>(&self, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> (C, SubGraph<'_, V, E, Self>) where Self: Sized {
+      let indexed = IndexedGraph::new(self);
+      let mut candidates = (0 .. self.m()).filter_map(|e| (f)(self.edge(e)).map(|c| (c, e) ) ).collect::<Vec<_>>();
+      candidates.sort();
+      let mut uf = UnionFind::new(indexed.vertices.len());
+      let mut cost = C::zero();
+      let mut edges = Vec::new();
+      for (c, e) in candidates {
+        let edge = self.edge(e);
+        if uf.merge(indexed.index[&edge.from()], indexed.index[&edge.to()]) {
+          edges.push(e);
+          edges.extend(self.reverse_edge(e));
+          cost += c;
+        }
+      }
+      (cost, SubGraph::new(self, indexed.vertices, edges))
+    }
+
+    fn minimum_spanning_tree_kruskal(&self) -> (E, SubGraph<'_, V, E, Self>) where Self: Sized, E: Measure {
+      self.minimum_spanning_tree_kruskal_by(|edge| Some(*edge.weight()) )
+    }
+
+    /// minimum spanning forest (Borůvka)
+    /// each phase picks the cheapest edge leaving every component, so there are `O(log n)` phases over all edges
+    /// ties are broken by edge id
+    fn minimum_spanning_tree_boruvka_by<C: Measure>(&self, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> (C, SubGraph<'_, V, E, Self>) where Self: Sized {
+      let indexed = IndexedGraph::new(self);
+      let n = indexed.vertices.len();
+      let candidates = (0 .. self.m()).filter_map(|e| {
+        let edge = self.edge(e);
+        (f)(edge).map(|c| (c, e, indexed.index[&edge.from()], indexed.index[&edge.to()]) )
+      }).collect::<Vec<_>>();
+      let mut uf = UnionFind::new(n);
+      let mut cost = C::zero();
+      let mut edges = Vec::new();
+      loop {
+        let mut cheapest: Vec<Option<(C, usize)>> = vec![None; n];
+        for &(c, e, u, v) in &candidates {
+          let (u, v) = (uf.leader(u), uf.leader(v));
+          if u == v { continue }
+          cheapest[u].chmin((c, e));
+          cheapest[v].chmin((c, e));
+        }
+        let mut merged = false;
+        for (c, e) in cheapest.into_iter().flatten() {
+          let edge = self.edge(e);
+          if uf.merge(indexed.index[&edge.from()], indexed.index[&edge.to()]) {
+            edges.push(e);
+            edges.extend(self.reverse_edge(e));
+            cost += c;
+            merged = true;
+          }
+        }
+        if !merged { break }
+      }
+      (cost, SubGraph::new(self, indexed.vertices, edges))
+    }
+
+    fn minimum_spanning_tree_boruvka(&self) -> (E, SubGraph<'_, V, E, Self>) where Self: Sized, E: Measure {
+      self.minimum_spanning_tree_boruvka_by(|edge| Some(*edge.weight()) )
+    }
+
+    /// minimum spanning arborescence rooted at `root` (Chu-Liu/Edmonds by Tarjan, `O(m log n)`)
+    /// returns the cost and the edge ids entering each vertex other than `root`, or `None` if some vertex is unreachable
+    fn minimum_arborescence_by<C: Measure>(&self, root: V, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> Option<(C, Vec<usize>)> {
+      let indexed = IndexedGraph::new(self);
+      let n = indexed.vertices.len();
+      let r = indexed.index[&root];
+      let mut from = vec![0; self.m()];
+      let mut to = vec![0; self.m()];
+      let mut heap = (0 .. n).map(|_| LeftistHeap::new() ).collect::<Vec<_>>();
+      for (u, list) in indexed.adj.iter().enumerate() {
+        for &(e, v) in list {
+          if u == v { continue }
+          if let Some(c) = (f)(self.edge(e)) {
+            from[e] = u;
+            to[e] = v;
+            heap[v].push(Reverse((c, e)));
+          }
+        }
+      }
+
+      // contracted cycles become new nodes `n ..`; `node[leader]` is the node of a component
+      let mut uf = UnionFind::new(n);
+      let mut node = (0 .. n).collect::<Vec<_>>();
+      let mut parent: Vec<Option<usize>> = vec![None; n];
+      let mut children: Vec<Vec<usize>> = vec![vec![]; n];
+      let mut in_edge: Vec<Option<usize>> = vec![None; n];
+      // the reduced cost of a key in `heap[u]` is `key - offset[u]`; offsets only grow, so unsigned costs never underflow
+      let mut offset = vec![C::zero(); n];
+      let mut seen = vec![usize::MAX; n];
+      seen[r] = r;
+      let mut cost = C::zero();
+      for s in 0 .. n {
+        let mut u = uf.leader(s);
+        let mut path = vec![];
+        while seen[u] == usize::MAX {
+          seen[u] = s;
+          path.push(u);
+          let (c, e) = loop {
+            let Reverse((c, e)) = heap[u].pop()?;
+            if uf.leader(from[e]) != u { break (c, e) }
+          };
+          cost += c - offset[u];
+          offset[u] = c;
+          in_edge[node[u]] = Some(e);
+          let w = uf.leader(from[e]);
+          if seen[w] != s {
+            u = w;
+            continue;
+          }
+          let k = parent.len();
+          parent.push(None);
+          children.push(vec![]);
+          in_edge.push(None);
+          let cycle = path.split_off(path.iter().rposition(|&x| x == w ).unwrap());
+          let top = cycle.iter().map(|&x| offset[x] ).max().unwrap();
+          let mut merged = LeftistHeap::new();
+          for &x in cycle.iter().rev() {
+            parent[node[x]] = Some(k);
+            children[k].push(node[x]);
+            let mut h = std::mem::take(&mut heap[x]);
+            h.apply(heap::Add(top - offset[x]));
+            merged.merge(h);
+            uf.merge(x, w);
+          }
+          u = uf.leader(w);
+          node[u] = k;
+          heap[u] = merged;
+          offset[u] = top;
+          seen[u] = usize::MAX;
+        }
+      }
+
+      // expand contracted nodes: the node entered from outside gives up its own edge, its siblings keep theirs
+      let mut edges = vec![];
+      let mut stack = vec![(r, r)];
+      for x in 0 .. parent.len() {
+        if parent[x].is_none() && x != r {
+          let e = in_edge[x].unwrap();
+          edges.push(e);
+          stack.push((x, to[e]));
+        }
+      }
+      while let Some((top, mut x)) = stack.pop() {
+        let mut prev = None;
+        loop {
+          for &y in &children[x] {
+            if Some(y) == prev { continue }
+            let e = in_edge[y].unwrap();
+            edges.push(e);
+            stack.push((y, to[e]));
+          }
+          if x == top { break }
+          prev = Some(x);
+          x = parent[x].unwrap();
+        }
+      }
+      Some((cost, edges))
+    }
+
+    fn minimum_arborescence(&self, root: V) -> Option<(E, Vec<usize>)> where E: Measure {
+      self.minimum_arborescence_by(root, |edge| Some(*edge.weight()) )
+    }
   }
   
   pub trait GraphMut<V: VertexId, E>: Graph<V, E> where Self::Vertex: VertexMut, Self::Edge: EdgeMut<V, E> {
@@ -280,6 +957,143 @@ pub mod graphs {
     queue: VecDeque<V>,
   }
   
+  /// distances from `source` and the last edge of a shortest path to each reached vertex
+  #[derive(Debug, Clone)]
+  pub struct ShortestPathTree<V: VertexId, C> {
+    source: V,
+    dist: FxHashMap<V, C>,
+    prev: FxHashMap<V, (usize, V)>,
+  }
+  impl<V: VertexId, C: Copy> ShortestPathTree<V, C> {
+    fn new(source: V) -> Self where C: Measure {
+      let mut dist = FxHashMap::default();
+      dist.insert(source, C::zero());
+      Self { source, dist, prev: FxHashMap::default() }
+    }
+
+    fn set(&mut self, v: V, d: C, e: usize, from: V) {
+      self.dist.insert(v, d);
+      self.prev.insert(v, (e, from));
+    }
+
+    /// update if `d` is shorter than the current distance to `v`
+    fn relax(&mut self, v: V, d: C, e: usize, from: V) -> bool where C: Measure {
+      let mut updated = false;
+      let prev = &mut self.prev;
+      self.dist.if_chmin(v, d, || { prev.insert(v, (e, from)); updated = true; });
+      updated
+    }
+
+    pub fn source(&self) -> V { self.source }
+    pub fn dist(&self) -> &FxHashMap<V, C> { &self.dist }
+    pub fn into_dist(self) -> FxHashMap<V, C> { self.dist }
+
+    /// distance to `v` (`None` if unreachable)
+    pub fn distance(&self, v: V) -> Option<C> { self.dist.get(&v).copied() }
+
+    /// id of the last edge on the shortest path to `v`
+    pub fn prev_edge(&self, v: V) -> Option<usize> { self.prev.get(&v).map(|&(e, _)| e ) }
+
+    /// edge ids of a shortest path from `source` to `v`, usable with `Graph::edge`
+    /// empty if `v` is `source` or unreachable
+    pub fn path_to(&self, mut v: V) -> Vec<usize> {
+      let mut path = vec![];
+      while let Some(&(e, u)) = self.prev.get(&v) {
+        path.push(e);
+        v = u;
+        if v == self.source { break }
+      }
+      path.reverse();
+      path
+    }
+  }
+
+  /// reason why no eulerian trail exists
+  #[derive(Debug, Clone, PartialEq, Eq)]
+  pub enum EulerianTrailError<V> {
+    /// undirected: vertices of odd degree (neither zero nor two of them)
+    OddDegree(Vec<V>),
+    /// directed: vertices with `out-degree - in-degree` not matching `(0, ..)` or a `(1, -1)` pair
+    Unbalanced(Vec<(V, isize)>),
+    /// edges are not all in one connected component
+    Disconnected,
+    /// undirected: arc without a reverse arc to pair with
+    Unpaired(usize),
+  }
+
+  /// negative cycle reachable from the source of shortest path
+  #[derive(Debug, Clone)]
+  pub struct NegativeCycle<V: VertexId, C> {
+    cycle: Vec<usize>,
+    unbounded: FxHashSet<V>,
+    dist: FxHashMap<V, C>,
+  }
+  impl<V: VertexId, C> NegativeCycle<V, C> {
+    /// edge ids of one negative cycle, in order
+    pub fn cycle(&self) -> &[usize] { &self.cycle }
+
+    /// vertices whose distance is `-∞`
+    pub fn unbounded(&self) -> &FxHashSet<V> { &self.unbounded }
+    pub fn is_unbounded(&self, v: V) -> bool { self.unbounded.contains(&v) }
+
+    /// distances to reachable vertices which are not unbounded
+    pub fn dist(&self) -> &FxHashMap<V, C> { &self.dist }
+  }
+
+  /// all pairs shortest paths stored in a flat matrix indexed by `index(v)`
+  #[derive(Debug, Clone)]
+  pub struct DistanceMatrix<V: VertexId, C> {
+    vertices: Vec<V>,
+    index: FxHashMap<V, usize>,
+    dist: Vec<Option<C>>,
+    prev: Vec<Option<(usize, usize)>>,
+    potential: Option<Vec<C>>,
+  }
+  impl<V: VertexId, C: Copy> DistanceMatrix<V, C> {
+    /// Number of vertices
+    pub fn n(&self) -> usize { self.vertices.len() }
+
+    /// row/column of `v`: vertices in `each_vertex` order, followed by arc targets that were never registered
+    pub fn index(&self, v: V) -> usize { self.index[&v] }
+    pub fn vertex(&self, index: usize) -> V { self.vertices[index] }
+
+    /// distance from `u` to `v` (`None` if unreachable)
+    pub fn get(&self, u: V, v: V) -> Option<C> { self.dist[self.index(u) * self.n() + self.index(v)] }
+
+    /// row-major `n * n` matrix of distances
+    pub fn as_slice(&self) -> &[Option<C>] { &self.dist }
+
+    /// potential of `v` used by Johnson's algorithm (`None` for Floyd-Warshall)
+    /// `potential(u) + c >= potential(v)` holds for every arc `u -> v` of cost `c`
+    pub fn potential(&self, v: V) -> Option<C> { self.potential.as_ref().map(|p| p[self.index(v)] ) }
+
+    /// edge ids of a shortest path from `u` to `v`
+    pub fn path(&self, u: V, v: V) -> Option<Vec<usize>> {
+      let (n, i) = (self.n(), self.index(u));
+      let mut j = self.index(v);
+      self.dist[i * n + j]?;
+      let mut path = vec![];
+      while let Some((e, k)) = self.prev[i * n + j] {
+        path.push(e);
+        j = k;
+        if j == i { break }
+      }
+      path.reverse();
+      Some(path)
+    }
+
+    pub fn into_nested(self) -> FxHashMap<V, FxHashMap<V, C>> {
+      let n = self.n();
+      let mut nested = FxHashMap::default();
+      for (i, &u) in self.vertices.iter().enumerate() {
+        for (j, &v) in self.vertices.iter().enumerate() {
+          if let Some(d) = self.dist[i * n + j] { nested.entry(u).or_insert_with(FxHashMap::default).insert(v, d); }
+        }
+      }
+      nested
+    }
+  }
+
   pub mod dic_graph {
     use std::marker::PhantomData;
     use std::collections::*;
@@ -368,6 +1182,14 @@ pub mod graphs {
       }
 
       fn reverse_edge(&self, e: usize) -> Option<usize> { self.edge(e).rev }
+
+      fn shortest_paths_floyd_warshall_by<C: Measure>(&self, loops: bool, mut f: impl FnMut(&Self::Edge) -> Option<C>) -> FxHashMap<V, FxHashMap<V, C>> {
+        if !D::DENSE { return super::floyd_warshall_nested(self, loops, f) }
+        match self.shortest_paths_floyd_warshall_dense_by(loops, &mut f) {
+          Ok(dist) => dist.into_nested(),
+          Err(_) => super::floyd_warshall_nested(self, loops, f),
+        }
+      }
     }
     impl<D: Dic<V, Vertex>, V: VertexId, E> GraphMut<V, E> for DicGraph<D, V, E> {
       fn add_arc(&mut self, from: V, to: V, weight: E) -> usize {
@@ -506,7 +1328,7 @@ pub mod graphs {
       type Edge = Edge;
 
       fn n(&self) -> usize { self.rows * self.columns }
-      fn m(&self) -> usize { (self.rows * self.columns - self.rows - self.columns) * 2 }
+      fn m(&self) -> usize { (self.rows * (self.columns - 1) + (self.rows - 1) * self.columns) * 2 }
 
       fn each_vertex(&self, mut f: impl FnMut((usize, usize))) { for i in 0 .. self.rows { for j in 0 .. self.columns { (f)((i, j)) } } }
 
@@ -522,7 +1344,7 @@ pub mod graphs {
           ((i, j), (i, j + 1))
         } else {
           let f = f - self.rows * (self.columns - 1);
-          let (i, j) = (f / self.columns, f / self.columns);
+          let (i, j) = (f / self.columns, f % self.columns);
           ((i, j), (i + 1, j))
         };
         if (e & 1) == 1 {
@@ -535,10 +1357,10 @@ pub mod graphs {
         assert!(self.valid_vertex(from));
         let mut edges = vec![];
         let (i, j) = from;
-        if j + 1 < self.columns { edges.push(i * (self.columns - 1) + (j - 1) << 1 | 0) }
-        if j > 1 { edges.push(i * (self.columns - 1) + j << 1 | 1) }
-        if i > 1 { edges.push(self.rows * (self.columns - 1) + i * self.columns + j << 1 | 0) }
-        if i + 1 < self.rows { edges.push(self.rows * (self.columns - 1) + (i - 1) * self.columns + j << 1 | 0) }
+        if j + 1 < self.columns { edges.push((i * (self.columns - 1) + j) << 1) }
+        if j >= 1 { edges.push((i * (self.columns - 1) + (j - 1)) << 1 | 1) }
+        if i + 1 < self.rows { edges.push((self.rows * (self.columns - 1) + i * self.columns + j) << 1) }
+        if i >= 1 { edges.push((self.rows * (self.columns - 1) + (i - 1) * self.columns + j) << 1 | 1) }
         edges
       }
 
@@ -547,9 +1369,9 @@ pub mod graphs {
         let mut vertices = vec![];
         let (i, j) = from;
         if j + 1 < self.columns { vertices.push((i, j + 1)) }
-        if j > 1 { vertices.push((i, j - 1)) }
-        if i > 1 { vertices.push((i + 1, j)) }
-        if i + 1 < self.rows { vertices.push((i - 1, j)) }
+        if j >= 1 { vertices.push((i, j - 1)) }
+        if i + 1 < self.rows { vertices.push((i + 1, j)) }
+        if i >= 1 { vertices.push((i - 1, j)) }
         vertices
       }
     }
@@ -565,29 +1387,1583 @@ pub mod graphs {
     }
   }
   
-  impl<V: VertexId> Walker<V> {
-    pub fn new() -> Self { Self { visited: FxHashSet::default(), queue: VecDeque::new() } }
-    pub fn go_next(&mut self, v: V) -> bool { self.visited.insert(v) && { self.queue.push_back(v); true } }
-    pub fn go_later(&mut self, v: V) -> bool { self.visited.insert(v) && { self.queue.push_front(v); true } }
-    pub fn forget(&mut self, v: V) -> bool { self.visited.remove(&v) }
-    pub fn is_visited(&self, v: V) -> bool { self.visited.contains(&v) }
-  }
-  impl<V: VertexId> Iterator for Walker<V> {
-    type Item = V;
-    fn next(&mut self) -> Option<Self::Item> { self.queue.pop_back() }
-  }
+  pub mod two_sat {
+    use super::{Graph, GraphMut, VecGraph};
 
-  impl<V, E> IntoEdge<V, E> for (V, V, E) {
-    fn into_edge(self) -> (V, V, E) { self }
-  }
-  impl<V, E: Default> IntoEdge<V, E> for (V, V) {
-    fn into_edge(self) -> (V, V, E) { (self.0, self.1, Default::default()) }
-  }
-  
-  pub mod measure {
-    use std::cmp::*;
-    use num_traits::*;
-    
+    /// 2-SAT solved on the implication graph
+    /// literal `(i, f)` means `x[i] == f`
+    #[derive(Debug, Clone)]
+    pub struct TwoSat {
+      n: usize,
+      graph: VecGraph,
+    }
+    impl TwoSat {
+      pub fn new(n: usize) -> Self {
+        let mut two_sat = Self { n: 0, graph: VecGraph::new() };
+        for _ in 0 .. n { two_sat.add_variable(); }
+        two_sat
+      }
+
+      /// Number of variables
+      pub fn n(&self) -> usize { self.n }
+
+      pub fn graph(&self) -> &VecGraph { &self.graph }
+
+      /// add a new variable and return its index
+      pub fn add_variable(&mut self) -> usize {
+        self.n += 1;
+        self.graph.vertex_mut(2 * self.n - 1);
+        self.n - 1
+      }
+
+      /// `x[i] == f || x[j] == g`
+      pub fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        let (a, b) = (self.literal(i, f), self.literal(j, g));
+        self.graph.connect(a ^ 1, b);
+        self.graph.connect(b ^ 1, a);
+      }
+
+      /// `x[i] == f` implies `x[j] == g`
+      pub fn add_implication(&mut self, i: usize, f: bool, j: usize, g: bool) { self.add_clause(i, !f, j, g) }
+
+      /// `(x[i] == f) == (x[j] == g)`
+      pub fn add_equality(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        self.add_implication(i, f, j, g);
+        self.add_implication(j, g, i, f);
+      }
+
+      /// `x[i] == f`
+      pub fn fix(&mut self, i: usize, f: bool) { self.add_clause(i, f, i, f) }
+
+      /// at most one of `literals` holds
+      /// uses `literals.len()` auxiliary variables (prefix-or encoding)
+      pub fn add_at_most_one(&mut self, literals: &[(usize, bool)]) {
+        let mut prev: Option<usize> = None;
+        for &(i, f) in literals {
+          let s = self.add_variable();
+          self.add_implication(i, f, s, true);
+          if let Some(p) = prev {
+            self.add_implication(p, true, s, true);
+            self.add_implication(p, true, i, !f);
+          }
+          prev = Some(s);
+        }
+      }
+
+      /// returns an assignment if satisfiable
+      pub fn satisfiable(&self) -> Option<Vec<bool>> {
+        let (id, _) = self.graph.strongly_connected_components();
+        (0 .. self.n).map(|i| {
+          let (t, f) = (id[&(2 * i + 1)], id[&(2 * i)]);
+          if t == f { None } else { Some(t > f) }
+        }).collect()
+      }
+
+      fn literal(&self, i: usize, f: bool) -> usize {
+        assert!(i < self.n);
+        2 * i + f as usize
+      }
+    }
+  }
+  
+  pub mod lowlink {
+    use super::{Graph, GraphMut, VecGraph, VertexId, IndexedGraph};
+    use rustc_hash::FxHashMap;
+
+    /// result of lowlink on undirected graph
+    /// multi-edge safe: only the reverse arc of the tree arc is excluded from back edges
+    #[derive(Debug, Clone)]
+    pub struct Lowlink<V> {
+      vertices: Vec<V>,
+      index: FxHashMap<V, usize>,
+      ord: Vec<usize>,
+      low: Vec<usize>,
+      bridges: Vec<(usize, usize, usize)>,
+      articulation_points: Vec<V>,
+      two_edge: Vec<usize>,
+      two_edge_count: usize,
+      blocks: Vec<Vec<usize>>,
+      block_edges: Vec<Vec<usize>>,
+    }
+    impl<V: VertexId> Lowlink<V> {
+      pub fn new<E, G: Graph<V, E> + ?Sized>(graph: &G) -> Self {
+        let IndexedGraph { vertices, index, adj } = IndexedGraph::new(graph);
+        let n = vertices.len();
+        let mut ord = vec![n; n];
+        let mut low = vec![n; n];
+        let mut tree_arc = vec![None; n];
+        let mut children = vec![0; n];
+        let mut two_edge = vec![n; n];
+        let mut two_edge_count = 0;
+        let mut bridges = vec![];
+        let mut blocks = vec![];
+        let mut block_edges = vec![];
+        let mut in_block = vec![false; n];
+        let mut vertex_stack = vec![];
+        let mut edge_stack: Vec<(usize, usize, usize)> = vec![];
+        let mut path: Vec<(usize, Option<usize>, usize)> = vec![];
+        let mut time = 0;
+        for s in 0 .. n {
+          if ord[s] != n { continue }
+          ord[s] = time;
+          low[s] = time;
+          time += 1;
+          vertex_stack.push(s);
+          path.push((s, None, 0));
+          while let Some(&(u, skip, i)) = path.last() {
+            if let Some(&(e, v)) = adj[u].get(i) {
+              path.last_mut().unwrap().2 += 1;
+              if skip == Some(e) { continue }
+              if ord[v] == n {
+                ord[v] = time;
+                low[v] = time;
+                time += 1;
+                tree_arc[v] = Some(e);
+                vertex_stack.push(v);
+                edge_stack.push((e, u, v));
+                path.push((v, graph.reverse_edge(e), 0));
+              } else {
+                low[u] = low[u].min(ord[v]);
+                if ord[v] < ord[u] { edge_stack.push((e, u, v)) }
+              }
+              continue;
+            }
+            path.pop();
+            if let Some(&(p, _, _)) = path.last() {
+              low[p] = low[p].min(low[u]);
+              let e = tree_arc[u].unwrap();
+              if low[u] > ord[p] { bridges.push((e, p, u)) }
+              if low[u] >= ord[p] {
+                children[p] += 1;
+                let mut block = vec![];
+                let mut edges = vec![];
+                while let Some((d, x, y)) = edge_stack.pop() {
+                  edges.push(d);
+                  for w in [x, y] {
+                    if !in_block[w] {
+                      in_block[w] = true;
+                      block.push(w);
+                    }
+                  }
+                  if d == e { break }
+                }
+                for &w in &block { in_block[w] = false }
+                blocks.push(block);
+                block_edges.push(edges);
+              }
+            } else if children[u] == 0 {
+              blocks.push(vec![u]);
+              block_edges.push(vec![]);
+            }
+            if low[u] == ord[u] {
+              while let Some(w) = vertex_stack.pop() {
+                two_edge[w] = two_edge_count;
+                if w == u { break }
+              }
+              two_edge_count += 1;
+            }
+          }
+        }
+        let articulation_points = (0 .. n).filter(|&u| children[u] >= if tree_arc[u].is_none() { 2 } else { 1 } ).map(|u| vertices[u] ).collect::<Vec<_>>();
+        Self { vertices, index, ord, low, bridges, articulation_points, two_edge, two_edge_count, blocks, block_edges }
+      }
+
+      /// vertex renumbered into `0 .. n` (used by `block_cut_tree`)
+      pub fn index(&self, v: V) -> usize { self.index[&v] }
+      pub fn vertex(&self, index: usize) -> V { self.vertices[index] }
+
+      /// order of `v` in DFS
+      pub fn ord(&self, v: V) -> usize { self.ord[self.index[&v]] }
+      /// minimum `ord` reachable from subtree of `v` using at most one back edge
+      pub fn low(&self, v: V) -> usize { self.low[self.index[&v]] }
+
+      /// edge ids of bridges (the arc used in DFS tree)
+      pub fn bridges(&self) -> Vec<usize> { self.bridges.iter().map(|&(e, _, _)| e ).collect() }
+
+      pub fn articulation_points(&self) -> &[V] { &self.articulation_points }
+
+      /// returns `(id, tree)`
+      /// `id[v]` is the two-edge-connected component of `v`
+      /// `tree` connects components by bridges (one forest edge per bridge)
+      pub fn two_edge_connected_components(&self) -> (FxHashMap<V, usize>, VecGraph) {
+        let mut tree = VecGraph::new();
+        for c in 0 .. self.two_edge_count { tree.vertex_mut(c); }
+        for &(_, u, v) in &self.bridges { tree.add_edge(self.two_edge[u], self.two_edge[v], ()); }
+        let id = self.vertices.iter().zip(&self.two_edge).map(|(&v, &c)| (v, c) ).collect::<FxHashMap<_, _>>();
+        (id, tree)
+      }
+
+      /// vertices of each biconnected component (isolated vertex forms a component by itself)
+      pub fn biconnected_components(&self) -> Vec<Vec<V>> {
+        self.blocks.iter().map(|block| block.iter().map(|&u| self.vertices[u] ).collect() ).collect()
+      }
+
+      /// edge ids of each biconnected component, in the same order as `biconnected_components`
+      pub fn biconnected_component_edges(&self) -> &[Vec<usize>] { &self.block_edges }
+
+      /// block-cut tree
+      /// vertex `index(v)` for each original vertex `v` and vertex `n + b` for `b`-th biconnected component
+      pub fn block_cut_tree(&self) -> VecGraph {
+        let n = self.vertices.len();
+        let mut tree = VecGraph::new();
+        for x in 0 .. n + self.blocks.len() { tree.vertex_mut(x); }
+        for (b, block) in self.blocks.iter().enumerate() {
+          for &u in block { tree.add_edge(u, n + b, ()); }
+        }
+        tree
+      }
+    }
+  }
+  
+  pub mod bipartite {
+    use super::{Graph, VertexId, IndexedGraph};
+    use rustc_hash::FxHashMap;
+    use std::collections::VecDeque;
+
+    /// 2-coloring by BFS, or edge ids of an odd cycle
+    pub(super) fn coloring(adj: &[Vec<(usize, usize)>]) -> Result<Vec<bool>, Vec<usize>> {
+      let n = adj.len();
+      let mut color = vec![None; n];
+      let mut parent = vec![None; n];
+      let mut depth = vec![0; n];
+      for s in 0 .. n {
+        if color[s].is_some() { continue }
+        color[s] = Some(false);
+        let mut queue = VecDeque::new();
+        queue.push_back(s);
+        while let Some(u) = queue.pop_front() {
+          for &(e, v) in &adj[u] {
+            match color[v] {
+              None => {
+                color[v] = Some(!color[u].unwrap());
+                parent[v] = Some((e, u));
+                depth[v] = depth[u] + 1;
+                queue.push_back(v);
+              }
+              Some(c) if c == color[u].unwrap() => {
+                let (mut x, mut y) = (u, v);
+                let (mut left, mut right) = (vec![], vec![]);
+                while depth[x] > depth[y] { let (d, p) = parent[x].unwrap(); left.push(d); x = p; }
+                while depth[y] > depth[x] { let (d, p) = parent[y].unwrap(); right.push(d); y = p; }
+                while x != y {
+                  let (d, p) = parent[x].unwrap(); left.push(d); x = p;
+                  let (d, p) = parent[y].unwrap(); right.push(d); y = p;
+                }
+                left.reverse();
+                left.push(e);
+                left.extend(right);
+                return Err(left);
+              }
+              _ => {}
+            }
+          }
+        }
+      }
+      Ok(color.into_iter().map(Option::unwrap).collect())
+    }
+
+    /// maximum matching of bipartite graph with König's theorem
+    #[derive(Debug, Clone)]
+    pub struct BipartiteMatching<V> {
+      vertices: Vec<V>,
+      index: FxHashMap<V, usize>,
+      color: Vec<bool>,
+      mate: Vec<Option<(usize, usize)>>,
+      cover: Vec<bool>,
+    }
+    impl<V: VertexId> BipartiteMatching<V> {
+      pub fn new<E, G: Graph<V, E> + ?Sized>(graph: &G) -> Result<Self, Vec<usize>> {
+        let indexed = IndexedGraph::new(graph);
+        let adj = indexed.undirected();
+        let color = coloring(&adj)?;
+        let n = adj.len();
+        // keep only arcs from left (`false`) to right
+        let adj = adj.into_iter().enumerate().map(|(u, list)| if color[u] { vec![] } else { list } ).collect::<Vec<_>>();
+        let mut mate: Vec<Option<(usize, usize)>> = vec![None; n];
+        let inf = usize::MAX;
+        let mut dist = vec![inf; n];
+        let mut it = vec![0; n];
+        loop {
+          let mut queue = VecDeque::new();
+          for u in 0 .. n {
+            dist[u] = inf;
+            if !color[u] && mate[u].is_none() {
+              dist[u] = 0;
+              queue.push_back(u);
+            }
+          }
+          let mut found = false;
+          while let Some(u) = queue.pop_front() {
+            for &(_, v) in &adj[u] {
+              match mate[v] {
+                None => found = true,
+                Some((_, w)) => if dist[w] == inf {
+                  dist[w] = dist[u] + 1;
+                  queue.push_back(w);
+                }
+              }
+            }
+          }
+          if !found { break }
+          it.iter_mut().for_each(|i| *i = 0 );
+          for s in 0 .. n {
+            if color[s] || mate[s].is_some() { continue }
+            let mut stack = vec![s];
+            while let Some(&u) = stack.last() {
+              let v = if let Some(&(_, v)) = adj[u].get(it[u]) { v } else {
+                dist[u] = inf;
+                stack.pop();
+                continue;
+              };
+              match mate[v] {
+                None => {
+                  for &x in &stack {
+                    let (e, y) = adj[x][it[x]];
+                    mate[x] = Some((e, y));
+                    mate[y] = Some((e, x));
+                  }
+                  break;
+                }
+                Some((_, w)) if dist[w] == dist[u] + 1 => stack.push(w),
+                _ => it[u] += 1,
+              }
+            }
+          }
+        }
+        // vertices reachable from free left vertices by alternating paths
+        let mut reached = vec![false; n];
+        let mut stack = (0 .. n).filter(|&u| !color[u] && mate[u].is_none() ).collect::<Vec<_>>();
+        for &u in &stack { reached[u] = true }
+        while let Some(u) = stack.pop() {
+          for &(_, v) in &adj[u] {
+            if reached[v] { continue }
+            reached[v] = true;
+            if let Some((_, w)) = mate[v] {
+              if !reached[w] {
+                reached[w] = true;
+                stack.push(w);
+              }
+            }
+          }
+        }
+        let cover = (0 .. n).map(|u| reached[u] == color[u] ).collect();
+        Ok(Self { vertices: indexed.vertices, index: indexed.index, color, mate, cover })
+      }
+
+      /// size of the maximum matching
+      pub fn size(&self) -> usize { (0 .. self.vertices.len()).filter(|&u| !self.color[u] && self.mate[u].is_some() ).count() }
+
+      /// edge ids of the matching
+      pub fn edges(&self) -> Vec<usize> {
+        (0 .. self.vertices.len()).filter(|&u| !self.color[u] ).filter_map(|u| self.mate[u].map(|(e, _)| e ) ).collect()
+      }
+
+      /// side of `v` in the 2-coloring
+      pub fn color(&self, v: V) -> bool { self.color[self.index[&v]] }
+
+      pub fn mate(&self, v: V) -> Option<V> { self.mate[self.index[&v]].map(|(_, u)| self.vertices[u] ) }
+
+      /// minimum vertex cover (König's theorem)
+      pub fn minimum_vertex_cover(&self) -> Vec<V> {
+        (0 .. self.vertices.len()).filter(|&u| self.cover[u] ).map(|u| self.vertices[u] ).collect()
+      }
+
+      /// maximum independent set (complement of the minimum vertex cover)
+      pub fn maximum_independent_set(&self) -> Vec<V> {
+        (0 .. self.vertices.len()).filter(|&u| !self.cover[u] ).map(|u| self.vertices[u] ).collect()
+      }
+    }
+  }
+
+  pub mod general_matching {
+    use crate::modint::modint::ModInt;
+    use std::collections::VecDeque;
+
+    /// Edmonds' blossom algorithm; returns edge ids of a maximum matching
+    pub(super) fn blossom(adj: &[Vec<(usize, usize)>]) -> Vec<usize> {
+      let n = adj.len();
+      let mut mate: Vec<Option<usize>> = vec![None; n];
+      let mut mate_edge = vec![None; n];
+      for u in 0 .. n {
+        if mate[u].is_some() { continue }
+        if let Some(&(e, v)) = adj[u].iter().find(|&&(_, v)| v != u && mate[v].is_none() ) {
+          mate[u] = Some(v);
+          mate[v] = Some(u);
+          mate_edge[u] = Some(e);
+          mate_edge[v] = Some(e);
+        }
+      }
+      let mut search = Search { adj, mate, parent: vec![None; n], base: (0 .. n).collect(), used: vec![false; n], blossom: vec![false; n], queue: VecDeque::new() };
+      for root in 0 .. n {
+        if search.mate[root].is_some() { continue }
+        let mut v = search.find_path(root);
+        while let Some(x) = v {
+          let (px, e) = search.parent[x].unwrap();
+          let next = search.mate[px];
+          search.mate[x] = Some(px);
+          search.mate[px] = Some(x);
+          mate_edge[x] = Some(e);
+          mate_edge[px] = Some(e);
+          v = next;
+        }
+      }
+      (0 .. n).filter(|&u| matches!(search.mate[u], Some(v) if u < v) ).map(|u| mate_edge[u].unwrap() ).collect()
+    }
+
+    struct Search<'a> {
+      adj: &'a [Vec<(usize, usize)>],
+      mate: Vec<Option<usize>>,
+      parent: Vec<Option<(usize, usize)>>,
+      base: Vec<usize>,
+      used: Vec<bool>,
+      blossom: Vec<bool>,
+      queue: VecDeque<usize>,
+    }
+    impl Search<'_> {
+      /// end of an augmenting path from `root`
+      fn find_path(&mut self, root: usize) -> Option<usize> {
+        let n = self.adj.len();
+        self.used.iter_mut().for_each(|x| *x = false );
+        self.parent.iter_mut().for_each(|x| *x = None );
+        for i in 0 .. n { self.base[i] = i }
+        self.used[root] = true;
+        self.queue.clear();
+        self.queue.push_back(root);
+        while let Some(v) = self.queue.pop_front() {
+          for &(e, to) in &self.adj[v] {
+            if self.base[v] == self.base[to] || self.mate[v] == Some(to) { continue }
+            if to == root || self.mate[to].and_then(|m| self.parent[m] ).is_some() {
+              let b = self.lca(v, to);
+              self.blossom.iter_mut().for_each(|x| *x = false );
+              self.mark_path(v, b, to, e);
+              self.mark_path(to, b, v, e);
+              for i in 0 .. n {
+                if self.blossom[self.base[i]] {
+                  self.base[i] = b;
+                  if !self.used[i] {
+                    self.used[i] = true;
+                    self.queue.push_back(i);
+                  }
+                }
+              }
+            } else if self.parent[to].is_none() {
+              self.parent[to] = Some((v, e));
+              let m = if let Some(m) = self.mate[to] { m } else { return Some(to) };
+              self.used[m] = true;
+              self.queue.push_back(m);
+            }
+          }
+        }
+        None
+      }
+
+      fn lca(&self, mut a: usize, mut b: usize) -> usize {
+        let mut visited = vec![false; self.adj.len()];
+        loop {
+          a = self.base[a];
+          visited[a] = true;
+          match self.mate[a] {
+            Some(m) => a = self.parent[m].unwrap().0,
+            None => break,
+          }
+        }
+        loop {
+          b = self.base[b];
+          if visited[b] { return b }
+          b = self.parent[self.mate[b].unwrap()].unwrap().0;
+        }
+      }
+
+      fn mark_path(&mut self, mut v: usize, b: usize, mut child: usize, mut e: usize) {
+        while self.base[v] != b {
+          let m = self.mate[v].unwrap();
+          self.blossom[self.base[v]] = true;
+          self.blossom[self.base[m]] = true;
+          self.parent[v] = Some((child, e));
+          child = m;
+          let (p, d) = self.parent[m].unwrap();
+          e = d;
+          v = p;
+        }
+      }
+    }
+
+    /// rank of Tutte matrix with random entries modulo 998244353
+    pub(super) fn tutte_rank(adj: &[Vec<(usize, usize)>]) -> usize {
+      const MOD: i64 = 998_244_353;
+      let n = adj.len();
+      let mut seed = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_nanos() as u64 ).unwrap_or(0) | 1;
+      let mut random = move || { seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17; (seed % (MOD as u64 - 1)) as i64 + 1 };
+      let zero = ModInt::with_mod(0, MOD);
+      let mut mat = vec![vec![zero; n]; n];
+      for (u, list) in adj.iter().enumerate() {
+        for &(_, v) in list {
+          if u == v { continue }
+          let x = ModInt::with_mod(random(), MOD);
+          mat[u][v] = x;
+          mat[v][u] = -x;
+        }
+      }
+      let mut rank = 0;
+      for col in 0 .. n {
+        let pivot = if let Some(r) = (rank .. n).find(|&r| mat[r][col] != zero ) { r } else { continue };
+        mat.swap(rank, pivot);
+        let inv = ModInt::with_mod(1, MOD) / mat[rank][col];
+        let (top, bottom) = mat.split_at_mut(rank + 1);
+        let pivot_row = &top[rank];
+        for row in bottom {
+          if row[col] == zero { continue }
+          let c = row[col] * inv;
+          for (x, &y) in row[col ..].iter_mut().zip(&pivot_row[col ..]) { *x -= y * c }
+        }
+        rank += 1;
+      }
+      rank
+    }
+  }
+
+  pub mod hld {
+    use super::{Graph, Edge as _};
+    use std::ops::Range;
+
+    /// heavy-light decomposition of a tree on vertices `0 .. n`
+    /// vertex `v` is placed at `pos(v)`; heavy paths and subtrees are contiguous
+    /// tree edge `(parent(v), v)` is placed at `pos(v)`
+    #[derive(Debug, Clone)]
+    pub struct HeavyLightDecomposition {
+      parent: Vec<Option<usize>>,
+      depth: Vec<usize>,
+      size: Vec<usize>,
+      head: Vec<usize>,
+      pos: Vec<usize>,
+      order: Vec<usize>,
+    }
+    impl HeavyLightDecomposition {
+      pub fn new<E, G: Graph<usize, E> + ?Sized>(graph: &G, root: usize) -> Self {
+        let n = graph.n();
+        let mut parent = vec![None; n];
+        let mut depth = vec![0; n];
+        let mut size = vec![1; n];
+        let mut visited = vec![false; n];
+        let mut preorder = vec![root];
+        visited[root] = true;
+        let mut i = 0;
+        while i < preorder.len() {
+          let u = preorder[i];
+          graph.each_edge_from(u, |e| {
+            let v = graph.edge(e).to();
+            if visited[v] { return }
+            visited[v] = true;
+            parent[v] = Some(u);
+            depth[v] = depth[u] + 1;
+            preorder.push(v);
+          });
+          i += 1;
+        }
+        let mut heavy: Vec<Option<usize>> = vec![None; n];
+        for &v in preorder.iter().rev() {
+          if let Some(p) = parent[v] {
+            size[p] += size[v];
+            match heavy[p] {
+              Some(h) if size[h] >= size[v] => {}
+              _ => heavy[p] = Some(v),
+            }
+          }
+        }
+        let mut children = vec![vec![]; n];
+        for &v in &preorder {
+          if let Some(p) = parent[v] {
+            if heavy[p] != Some(v) { children[p].push(v) }
+          }
+        }
+        let mut head = (0 .. n).collect::<Vec<_>>();
+        let mut pos = vec![n; n];
+        let mut order = Vec::with_capacity(preorder.len());
+        let mut stack = vec![root];
+        while let Some(h) = stack.pop() {
+          let mut v = Some(h);
+          while let Some(u) = v {
+            head[u] = h;
+            pos[u] = order.len();
+            order.push(u);
+            for &c in children[u].iter().rev() { stack.push(c) }
+            v = heavy[u];
+          }
+        }
+        Self { parent, depth, size, head, pos, order }
+      }
+
+      pub fn pos(&self, v: usize) -> usize { self.pos[v] }
+      /// vertex placed at position `i`
+      pub fn vertex(&self, i: usize) -> usize { self.order[i] }
+      pub fn parent(&self, v: usize) -> Option<usize> { self.parent[v] }
+      pub fn depth(&self, v: usize) -> usize { self.depth[v] }
+      pub fn head(&self, v: usize) -> usize { self.head[v] }
+
+      /// positions of the subtree of `v`
+      pub fn subtree(&self, v: usize) -> Range<usize> { self.pos[v] .. self.pos[v] + self.size[v] }
+
+      pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.head[u] != self.head[v] {
+          if self.depth[self.head[u]] > self.depth[self.head[v]] {
+            u = self.parent[self.head[u]].unwrap();
+          } else {
+            v = self.parent[self.head[v]].unwrap();
+          }
+        }
+        if self.depth[u] < self.depth[v] { u } else { v }
+      }
+
+      /// number of edges between `u` and `v`
+      pub fn dist(&self, u: usize, v: usize) -> usize { self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)] }
+
+      /// `k`-th ancestor of `v`
+      pub fn la(&self, mut v: usize, mut k: usize) -> Option<usize> {
+        if k > self.depth[v] { return None }
+        loop {
+          let h = self.head[v];
+          if self.depth[v] - self.depth[h] >= k { return Some(self.order[self.pos[v] - k]) }
+          k -= self.depth[v] - self.depth[h] + 1;
+          v = self.parent[h].unwrap();
+        }
+      }
+
+      /// `k`-th vertex on the path from `u` to `v` (`jump(u, v, 0) == Some(u)`)
+      pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let w = self.lca(u, v);
+        let (du, dv) = (self.depth[u] - self.depth[w], self.depth[v] - self.depth[w]);
+        if k <= du {
+          self.la(u, k)
+        } else if k <= du + dv {
+          self.la(v, du + dv - k)
+        } else {
+          None
+        }
+      }
+
+      /// ranges of positions covering the vertices on the path from `u` to `v`, in order of the path
+      /// `(range, true)` is traversed from `range.end - 1` down to `range.start`, `(range, false)` upward
+      pub fn path_vertices(&self, u: usize, v: usize) -> Vec<(Range<usize>, bool)> { self.path(u, v, false) }
+
+      /// same as `path_vertices` for the edges on the path (edge `(parent(x), x)` is at `pos(x)`)
+      pub fn path_edges(&self, u: usize, v: usize) -> Vec<(Range<usize>, bool)> { self.path(u, v, true) }
+
+      fn path(&self, mut u: usize, mut v: usize, edge: bool) -> Vec<(Range<usize>, bool)> {
+        let mut up = vec![];
+        let mut down = vec![];
+        while self.head[u] != self.head[v] {
+          if self.depth[self.head[u]] > self.depth[self.head[v]] {
+            up.push((self.pos[self.head[u]] .. self.pos[u] + 1, true));
+            u = self.parent[self.head[u]].unwrap();
+          } else {
+            down.push((self.pos[self.head[v]] .. self.pos[v] + 1, false));
+            v = self.parent[self.head[v]].unwrap();
+          }
+        }
+        let skip = edge as usize;
+        if self.depth[u] > self.depth[v] {
+          up.push((self.pos[v] + skip .. self.pos[u] + 1, true));
+        } else {
+          down.push((self.pos[u] + skip .. self.pos[v] + 1, false));
+        }
+        up.extend(down.into_iter().rev());
+        up.retain(|(range, _)| range.start < range.end );
+        up
+      }
+    }
+  }
+
+  pub mod euler_tour {
+    use super::{Graph, Edge as _};
+    use std::ops::Range;
+
+    /// euler tour of a tree on vertices `0 .. n` with `O(1)` LCA by a sparse table
+    /// subtree of `v` is `tin(v) .. tout(v)` in preorder
+    #[derive(Debug, Clone)]
+    pub struct EulerTour {
+      tin: Vec<usize>,
+      tout: Vec<usize>,
+      first: Vec<usize>,
+      depth: Vec<usize>,
+      order: Vec<usize>,
+      tour: Vec<usize>,
+      table: Vec<Vec<usize>>,
+    }
+    impl EulerTour {
+      /// vertices unreachable from `root` are ignored
+      pub fn new<E, G: Graph<usize, E> + ?Sized>(graph: &G, root: usize) -> Self {
+        let n = graph.n();
+        let mut tin = vec![usize::MAX; n];
+        let mut tout = vec![usize::MAX; n];
+        let mut first = vec![usize::MAX; n];
+        let mut depth = vec![0; n];
+        let mut order = vec![root];
+        let mut tour = vec![root];
+        tin[root] = 0;
+        first[root] = 0;
+        let mut stack = vec![(root, graph.edges_from(root).into_iter())];
+        while let Some((u, edges)) = stack.last_mut() {
+          let u = *u;
+          if let Some(e) = edges.next() {
+            let v = graph.edge(e).to();
+            if tin[v] != usize::MAX { continue }
+            tin[v] = order.len();
+            first[v] = tour.len();
+            depth[v] = depth[u] + 1;
+            order.push(v);
+            tour.push(v);
+            stack.push((v, graph.edges_from(v).into_iter()));
+          } else {
+            tout[u] = order.len();
+            stack.pop();
+            if let Some(&(p, _)) = stack.last() { tour.push(p) }
+          }
+        }
+        let mut table = vec![(0 .. tour.len()).collect::<Vec<_>>()];
+        let mut k = 1;
+        while 2 * k <= tour.len() {
+          let prev = table.last().unwrap();
+          let row = (0 ..= tour.len() - 2 * k).map(|i| {
+            let (a, b) = (prev[i], prev[i + k]);
+            if depth[tour[a]] <= depth[tour[b]] { a } else { b }
+          }).collect();
+          table.push(row);
+          k *= 2;
+        }
+        Self { tin, tout, first, depth, order, tour, table }
+      }
+
+      /// preorder index of `v`
+      pub fn tin(&self, v: usize) -> usize { self.tin[v] }
+      /// end of the preorder indices of the subtree of `v`
+      pub fn tout(&self, v: usize) -> usize { self.tout[v] }
+      pub fn subtree(&self, v: usize) -> Range<usize> { self.tin[v] .. self.tout[v] }
+      /// vertices in preorder
+      pub fn order(&self) -> &[usize] { &self.order }
+      /// vertex sequence of the tour (length `2n - 1`)
+      pub fn tour(&self) -> &[usize] { &self.tour }
+      /// first position of `v` in the tour
+      pub fn first(&self, v: usize) -> usize { self.first[v] }
+      pub fn depth(&self, v: usize) -> usize { self.depth[v] }
+      /// depths along the tour
+      pub fn depth_sequence(&self) -> Vec<usize> { self.tour.iter().map(|&v| self.depth[v] ).collect() }
+      pub fn is_ancestor(&self, u: usize, v: usize) -> bool { self.tin[u] <= self.tin[v] && self.tin[v] < self.tout[u] }
+
+      pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut l, mut r) = (self.first[u], self.first[v]);
+        if l > r { std::mem::swap(&mut l, &mut r) }
+        let k = (0usize.leading_zeros() - (r - l + 1).leading_zeros() - 1) as usize;
+        let (a, b) = (self.table[k][l], self.table[k][r + 1 - (1 << k)]);
+        if self.depth[self.tour[a]] <= self.depth[self.tour[b]] { self.tour[a] } else { self.tour[b] }
+      }
+
+      pub fn dist(&self, u: usize, v: usize) -> usize { self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)] }
+    }
+  }
+
+  pub mod lca {
+    use super::{Graph, Edge as _, measure::Measure};
+
+    /// LCA by doubling on a forest on vertices `0 .. n`
+    /// `C` is the type of weighted depth
+    #[derive(Debug, Clone)]
+    pub struct LCA<C> {
+      table: Vec<Vec<usize>>,
+      depth: Vec<usize>,
+      weighted_depth: Vec<C>,
+      parent_edge: Vec<Option<usize>>,
+      root: Vec<usize>,
+      log: usize,
+    }
+    impl<C: Measure> LCA<C> {
+      pub fn new<G: Graph<usize, C> + ?Sized>(graph: &G, root: usize) -> Self {
+        Self::new_multiple(graph, &[root])
+      }
+
+      pub fn new_multiple<G: Graph<usize, C> + ?Sized>(graph: &G, roots: &[usize]) -> Self {
+        Self::new_by(graph, roots, |edge| *edge.weight() )
+      }
+
+      /// `f` returns the weight of the tree edge
+      pub fn new_by<E, G: Graph<usize, E> + ?Sized>(graph: &G, roots: &[usize], mut f: impl FnMut(&G::Edge) -> C) -> Self {
+        let n = graph.n();
+        let log = ((0usize.leading_zeros() - n.leading_zeros()) as usize).max(1);
+        let mut table = vec![(0 .. n).collect::<Vec<_>>()];
+        let mut depth = vec![0; n];
+        let mut weighted_depth = vec![C::zero(); n];
+        let mut parent_edge = vec![None; n];
+        let mut root = (0 .. n).collect::<Vec<_>>();
+        let mut stack = roots.to_vec();
+        let mut visited = vec![false; n];
+        for &root in roots { visited[root] = true }
+        while let Some(u) = stack.pop() {
+          graph.each_edge_from(u, |e| {
+            let edge = graph.edge(e);
+            let v = edge.to();
+            if visited[v] { return }
+            visited[v] = true;
+            table[0][v] = u;
+            depth[v] = depth[u] + 1;
+            weighted_depth[v] = weighted_depth[u] + (f)(edge);
+            parent_edge[v] = Some(e);
+            root[v] = root[u];
+            stack.push(v);
+          });
+        }
+
+        for k in 1 .. log {
+          table.push((0 .. n).map(|v| table[k - 1][table[k - 1][v]] ).collect());
+        }
+
+        Self { table, depth, weighted_depth, parent_edge, root, log }
+      }
+    }
+    impl LCA<usize> {
+      /// ignores edge weights (any `E`, e.g. `VecGraph<()>`); `dist` equals `len`
+      pub fn new_unweighted<E, G: Graph<usize, E> + ?Sized>(graph: &G, root: usize) -> Self {
+        Self::new_unweighted_multiple(graph, &[root])
+      }
+
+      pub fn new_unweighted_multiple<E, G: Graph<usize, E> + ?Sized>(graph: &G, roots: &[usize]) -> Self {
+        Self::new_by(graph, roots, |_| 1 )
+      }
+    }
+    impl<C: Measure> LCA<C> {
+      pub fn depth(&self, v: usize) -> usize { self.depth[v] }
+      pub fn weighted_depth(&self, v: usize) -> C { self.weighted_depth[v] }
+      pub fn parent(&self, v: usize) -> Option<usize> { self.parent_edge[v].map(|_| self.table[0][v] ) }
+      /// edge id of the tree edge `parent(v) -> v`
+      pub fn parent_edge(&self, v: usize) -> Option<usize> { self.parent_edge[v] }
+      /// root of the tree containing `v`
+      pub fn root(&self, v: usize) -> usize { self.root[v] }
+
+      /// panics if `u` and `v` are in different trees; so do the queries below that use it
+      pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        assert_eq!(self.root[u], self.root[v], "{} and {} are in different trees", u, v);
+        if self.depth[u] < self.depth[v] {
+          std::mem::swap(&mut u, &mut v);
+        }
+        u = self.ancestor(u, self.depth[u] - self.depth[v]);
+        if u == v {
+          return u;
+        }
+        for row in self.table.iter().rev() {
+          if row[u] != row[v] {
+            u = row[u];
+            v = row[v];
+          }
+        }
+        self.table[0][u]
+      }
+
+      /// number of edges on the path
+      pub fn len(&self, u: usize, v: usize) -> usize { self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)] }
+
+      /// weighted distance
+      pub fn dist(&self, u: usize, v: usize) -> C {
+        let w = self.weighted_depth[self.lca(u, v)];
+        self.weighted_depth[u] - w + self.weighted_depth[v] - w
+      }
+
+      fn ancestor(&self, mut v: usize, k: usize) -> usize {
+        for i in 0 .. self.log {
+          if k >> i & 1 != 0 { v = self.table[i][v] }
+        }
+        v
+      }
+
+      pub fn kth_ancestor(&self, v: usize, k: usize) -> Option<usize> {
+        if k > self.depth[v] { return None }
+        Some(self.ancestor(v, k))
+      }
+
+      /// `k`-th vertex on the path from `u` to `v` (`0`-th is `u`)
+      pub fn kth_on_path(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let l = self.lca(u, v);
+        let (du, dv) = (self.depth[u] - self.depth[l], self.depth[v] - self.depth[l]);
+        if k <= du {
+          Some(self.ancestor(u, k))
+        } else if k <= du + dv {
+          Some(self.ancestor(v, du + dv - k))
+        } else {
+          None
+        }
+      }
+
+      /// doubling table to fold edge values along paths with a monoid `(e, op)`
+      /// `values[v]` is the value of the tree edge `parent(v) -> v`; it is ignored for roots
+      pub fn path_fold<T: Clone, F: Fn(&T, &T) -> T>(&self, values: Vec<T>, e: T, op: F) -> PathFold<'_, C, T, F> {
+        let n = values.len();
+        let mut up = vec![(0 .. n).map(|v| if self.parent_edge[v].is_some() { values[v].clone() } else { e.clone() } ).collect::<Vec<_>>()];
+        let mut down = up.clone();
+        for k in 1 .. self.log {
+          let mid = &self.table[k - 1];
+          let row_up = (0 .. n).map(|v| (op)(&up[k - 1][v], &up[k - 1][mid[v]]) ).collect();
+          let row_down = (0 .. n).map(|v| (op)(&down[k - 1][mid[v]], &down[k - 1][v]) ).collect();
+          up.push(row_up);
+          down.push(row_down);
+        }
+        PathFold { lca: self, e, op, up, down }
+      }
+    }
+
+    /// folds edge values along paths of a tree; see `LCA::path_fold`
+    pub struct PathFold<'a, C, T, F> {
+      lca: &'a LCA<C>,
+      e: T,
+      op: F,
+      up: Vec<Vec<T>>,
+      down: Vec<Vec<T>>,
+    }
+    impl<'a, C: Measure, T: Clone, F: Fn(&T, &T) -> T> PathFold<'a, C, T, F> {
+      /// fold of edge values along the path from `u` to `v`, in this order
+      pub fn fold(&self, u: usize, v: usize) -> T {
+        let lca = self.lca;
+        let l = lca.lca(u, v);
+        let mut left = self.e.clone();
+        let (mut x, d) = (u, lca.depth[u] - lca.depth[l]);
+        for k in 0 .. lca.log {
+          if d >> k & 1 != 0 {
+            left = (self.op)(&left, &self.up[k][x]);
+            x = lca.table[k][x];
+          }
+        }
+        let mut right = self.e.clone();
+        let (mut x, d) = (v, lca.depth[v] - lca.depth[l]);
+        for k in 0 .. lca.log {
+          if d >> k & 1 != 0 {
+            right = (self.op)(&self.down[k][x], &right);
+            x = lca.table[k][x];
+          }
+        }
+        (self.op)(&left, &right)
+      }
+    }
+  }
+
+  pub mod functional {
+    use super::{Graph, Edge as _};
+
+    /// functional graph on vertices `0 .. n` (each vertex has exactly one outgoing arc)
+    /// each weakly connected component is a cycle with in-trees hanging on it
+    #[derive(Debug, Clone)]
+    pub struct FunctionalGraph {
+      table: Vec<Vec<usize>>,
+      cycles: Vec<Vec<usize>>,
+      cycle_id: Vec<usize>,
+      cycle_pos: Vec<Option<usize>>,
+      entry: Vec<usize>,
+      dist: Vec<usize>,
+    }
+    impl FunctionalGraph {
+      /// `next[v]` is the successor of `v`
+      pub fn new(next: Vec<usize>) -> Self {
+        let n = next.len();
+        let mut cycles = vec![];
+        let mut cycle_id = vec![n; n];
+        let mut cycle_pos = vec![None; n];
+        let mut entry = vec![n; n];
+        let mut dist = vec![0; n];
+        let mut state = vec![0u8; n];
+        let mut path = vec![];
+        for s in 0 .. n {
+          if state[s] != 0 { continue }
+          let mut u = s;
+          while state[u] == 0 {
+            state[u] = 1;
+            path.push(u);
+            u = next[u];
+          }
+          if state[u] == 1 {
+            let i = path.iter().rposition(|&v| v == u ).unwrap();
+            let cycle = path.split_off(i);
+            for (j, &v) in cycle.iter().enumerate() {
+              state[v] = 2;
+              cycle_id[v] = cycles.len();
+              cycle_pos[v] = Some(j);
+              entry[v] = v;
+            }
+            cycles.push(cycle);
+          }
+          while let Some(v) = path.pop() {
+            let w = next[v];
+            state[v] = 2;
+            cycle_id[v] = cycle_id[w];
+            entry[v] = entry[w];
+            dist[v] = dist[w] + 1;
+          }
+        }
+        let log = ((0usize.leading_zeros() - n.leading_zeros()) as usize).max(1);
+        let mut table = vec![next];
+        for k in 1 .. log {
+          table.push((0 .. n).map(|v| table[k - 1][table[k - 1][v]] ).collect());
+        }
+        Self { table, cycles, cycle_id, cycle_pos, entry, dist }
+      }
+
+      /// panics unless each vertex has exactly one outgoing arc
+      pub fn from_graph<E, G: Graph<usize, E> + ?Sized>(graph: &G) -> Self {
+        Self::new((0 .. graph.n()).map(|v| {
+          let edges = graph.edges_from(v);
+          assert_eq!(edges.len(), 1, "vertex {} must have exactly one outgoing arc", v);
+          graph.edge(edges[0]).to()
+        }).collect())
+      }
+
+      /// undirected graph whose components each have exactly one cycle (なもりグラフ)
+      /// tree vertices point toward the cycle, and each cycle is oriented in one direction
+      pub fn from_namori<E, G: Graph<usize, E> + ?Sized>(graph: &G) -> Self {
+        let n = graph.n();
+        let mut next = (0 .. n).collect::<Vec<_>>();
+        let mut deg = (0 .. n).map(|v| graph.edges_from(v).len() ).collect::<Vec<_>>();
+        let mut removed = vec![false; n];
+        let mut stack = (0 .. n).filter(|&v| deg[v] == 1 ).collect::<Vec<_>>();
+        while let Some(u) = stack.pop() {
+          removed[u] = true;
+          graph.each_edge_from(u, |e| {
+            let w = graph.edge(e).to();
+            if removed[w] { return }
+            next[u] = w;
+            deg[w] -= 1;
+            if deg[w] == 1 { stack.push(w) }
+          });
+        }
+        for s in 0 .. n {
+          if removed[s] { continue }
+          let (mut prev, mut u) = (None, s);
+          while !removed[u] {
+            removed[u] = true;
+            let adj = graph.adjacent_vertices(u).into_iter().filter(|&w| deg[w] > 1 || w == u ).collect::<Vec<_>>();
+            let w = adj.iter().copied().find(|&w| Some(w) != prev ).or(prev).unwrap_or(u);
+            next[u] = w;
+            prev = Some(u);
+            u = w;
+          }
+        }
+        Self::new(next)
+      }
+
+      pub fn n(&self) -> usize { self.dist.len() }
+      pub fn next(&self, v: usize) -> usize { self.table[0][v] }
+      pub fn cycles(&self) -> &[Vec<usize>] { &self.cycles }
+      /// vertices of the cycle in order of `next`
+      pub fn cycle(&self, id: usize) -> &[usize] { &self.cycles[id] }
+      /// id of the cycle that `v` reaches
+      pub fn cycle_id(&self, v: usize) -> usize { self.cycle_id[v] }
+      /// position of `v` in its cycle, if `v` is on a cycle
+      pub fn cycle_pos(&self, v: usize) -> Option<usize> { self.cycle_pos[v] }
+      pub fn is_on_cycle(&self, v: usize) -> bool { self.cycle_pos[v].is_some() }
+      /// first vertex on the cycle reached from `v`
+      pub fn cycle_entry(&self, v: usize) -> usize { self.entry[v] }
+      /// number of steps until `v` first reaches the cycle
+      pub fn dist_to_cycle(&self, v: usize) -> usize { self.dist[v] }
+
+      /// vertex reached from `v` after `k` steps
+      pub fn jump(&self, mut v: usize, k: u64) -> usize {
+        if k > self.dist[v] as u64 {
+          let cycle = &self.cycles[self.cycle_id[v]];
+          let k = (k - self.dist[v] as u64) % cycle.len() as u64;
+          let i = self.cycle_pos[self.entry[v]].unwrap();
+          return cycle[(i + k as usize) % cycle.len()];
+        }
+        for (i, row) in self.table.iter().enumerate() {
+          if k >> i & 1 != 0 { v = row[v] }
+        }
+        v
+      }
+
+      /// minimum number of steps to reach `to` from `from`
+      pub fn first_reach(&self, from: usize, to: usize) -> Option<u64> {
+        if self.cycle_id[from] != self.cycle_id[to] { return None }
+        if let Some(j) = self.cycle_pos[to] {
+          let len = self.cycles[self.cycle_id[to]].len();
+          let i = self.cycle_pos[self.entry[from]].unwrap();
+          return Some((self.dist[from] + (j + len - i) % len) as u64);
+        }
+        if self.dist[from] < self.dist[to] { return None }
+        let k = (self.dist[from] - self.dist[to]) as u64;
+        if self.jump(from, k) == to { Some(k) } else { None }
+      }
+    }
+  }
+
+  pub mod dominator {
+    use super::{Graph, Edge as _};
+
+    /// dominator tree of a flow graph on vertices `0 .. n` (Lengauer-Tarjan, `O(m log n)`)
+    /// vertices unreachable from `root` are ignored
+    #[derive(Debug, Clone)]
+    pub struct DominatorTree {
+      root: usize,
+      idom: Vec<Option<usize>>,
+      tin: Vec<usize>,
+      tout: Vec<usize>,
+    }
+    impl DominatorTree {
+      pub fn new<E, G: Graph<usize, E> + ?Sized>(graph: &G, root: usize) -> Self {
+        let n = graph.n();
+        const NONE: usize = usize::MAX;
+        // vertices are renumbered in DFS preorder
+        let mut ord = vec![NONE; n];
+        let mut vertex = vec![];
+        let mut par = vec![];
+        let mut stack = vec![(root, NONE)];
+        while let Some((u, p)) = stack.pop() {
+          if ord[u] != NONE { continue }
+          ord[u] = vertex.len();
+          vertex.push(u);
+          par.push(p);
+          let edges = graph.edges_from(u);
+          for &e in edges.iter().rev() {
+            let v = graph.edge(e).to();
+            if ord[v] == NONE { stack.push((v, ord[u])) }
+          }
+        }
+        let k = vertex.len();
+        let mut preds = vec![vec![]; k];
+        for (i, &u) in vertex.iter().enumerate() {
+          graph.each_edge_from(u, |e| preds[ord[graph.edge(e).to()]].push(i) );
+        }
+
+        let mut semi = (0 .. k).collect::<Vec<_>>();
+        let mut label = (0 .. k).collect::<Vec<_>>();
+        let mut ancestor = vec![NONE; k];
+        let mut idom = vec![NONE; k];
+        let mut bucket = vec![vec![]; k];
+        let mut path = vec![];
+        let mut eval = |v: usize, ancestor: &mut Vec<usize>, label: &mut Vec<usize>, semi: &[usize]| -> usize {
+          if ancestor[v] == NONE { return v }
+          let mut x = v;
+          while ancestor[ancestor[x]] != NONE {
+            path.push(x);
+            x = ancestor[x];
+          }
+          while let Some(x) = path.pop() {
+            let a = ancestor[x];
+            if semi[label[a]] < semi[label[x]] { label[x] = label[a] }
+            ancestor[x] = ancestor[a];
+          }
+          label[v]
+        };
+        for w in (1 .. k).rev() {
+          for &v in &preds[w] {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            if semi[u] < semi[w] { semi[w] = semi[u] }
+          }
+          bucket[semi[w]].push(w);
+          let p = par[w];
+          ancestor[w] = p;
+          for v in std::mem::take(&mut bucket[p]) {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            idom[v] = if semi[u] < semi[v] { u } else { p };
+          }
+        }
+        for w in 1 .. k {
+          if idom[w] != semi[w] { idom[w] = idom[idom[w]] }
+        }
+
+        let mut children = vec![vec![]; k];
+        for w in 1 .. k { children[idom[w]].push(w) }
+        let mut tin = vec![NONE; n];
+        let mut tout = vec![NONE; n];
+        let mut time = 0;
+        let mut stack = vec![(0, false)];
+        while let Some((w, done)) = stack.pop() {
+          if done {
+            tout[vertex[w]] = time;
+            continue;
+          }
+          tin[vertex[w]] = time;
+          time += 1;
+          stack.push((w, true));
+          for &c in &children[w] { stack.push((c, false)) }
+        }
+        let mut result = vec![None; n];
+        for w in 1 .. k { result[vertex[w]] = Some(vertex[idom[w]]) }
+        Self { root, idom: result, tin, tout }
+      }
+
+      pub fn root(&self) -> usize { self.root }
+      /// immediate dominator; `None` for the root and unreachable vertices
+      pub fn idom(&self, v: usize) -> Option<usize> { self.idom[v] }
+      pub fn idoms(&self) -> &[Option<usize>] { &self.idom }
+      pub fn is_reachable(&self, v: usize) -> bool { self.tin[v] != usize::MAX }
+      /// whether every path from the root to `v` passes `u` (`u` dominates itself)
+      pub fn dominates(&self, u: usize, v: usize) -> bool {
+        self.is_reachable(u) && self.is_reachable(v) && self.tin[u] <= self.tin[v] && self.tin[v] < self.tout[u]
+      }
+    }
+  }
+
+  /// exponential algorithms on small graphs on vertices `0 .. n`
+  /// arcs are regarded as undirected edges and self-loops are ignored, except for hamiltonian paths
+  pub mod bitmask {
+    use super::{Graph, Edge as _, measure::Measure};
+    use crate::modint::modint::ModInt;
+
+    fn adjacency<E, G: Graph<usize, E> + ?Sized>(graph: &G) -> Vec<u64> {
+      let n = graph.n();
+      assert!(n <= 64);
+      let mut adj = vec![0u64; n];
+      for u in 0 .. n {
+        graph.each_edge_from(u, |e| {
+          let v = graph.edge(e).to();
+          if u != v {
+            adj[u] |= 1 << v;
+            adj[v] |= 1 << u;
+          }
+        });
+      }
+      adj
+    }
+
+    /// minimum number of colors, by inclusion-exclusion over independent sets in `O(2^n n)`
+    /// uses two tables of `2^n` `ModInt<i64>` (32 MiB for `n = 20`), so `n <= 20`
+    pub fn chromatic_number<E, G: Graph<usize, E> + ?Sized>(graph: &G) -> usize {
+      let n = graph.n();
+      if n == 0 { return 0 }
+      assert!(n <= 20);
+      let adj = adjacency(graph);
+      // the count of colorings is computed modulo two primes; a false zero only makes the answer larger, so take the minimum
+      // the answer is wrong only if the count is a multiple of both primes (about `10^-18` for a count that behaves randomly)
+      [998_244_353i64, 1_000_000_007].iter().map(|&modulus| {
+        let zero = ModInt::with_mod(0, modulus);
+        let one = ModInt::with_mod(1, modulus);
+        // number of independent sets in `s` (including the empty set)
+        let mut independent = vec![one; 1 << n];
+        for s in 1 .. 1usize << n {
+          let v = s.trailing_zeros() as usize;
+          independent[s] = independent[s & !(1 << v)] + independent[s & !(1 << v) & !adj[v] as usize];
+        }
+        let mut power = (0 .. 1usize << n).map(|s| if (n - s.count_ones() as usize) & 1 == 0 { one } else { -one } ).collect::<Vec<_>>();
+        (1 .. n).find(|_| {
+          let mut sum = zero;
+          for (p, &x) in power.iter_mut().zip(&independent) {
+            *p *= x;
+            sum += *p;
+          }
+          sum != zero
+        }).unwrap_or(n)
+      }).min().unwrap()
+    }
+
+    /// maximum independent set by branch and bound (`n <= 64`)
+    pub fn maximum_independent_set<E, G: Graph<usize, E> + ?Sized>(graph: &G) -> Vec<usize> {
+      let adj = adjacency(graph);
+      let all = if adj.len() == 64 { !0 } else { (1u64 << adj.len()) - 1 };
+      let mut best = 0;
+      independent_set(&adj, all, 0, &mut best);
+      (0 .. adj.len()).filter(|&v| best >> v & 1 != 0 ).collect()
+    }
+
+    /// maximum clique, as a maximum independent set of the complement (`n <= 64`)
+    pub fn maximum_clique<E, G: Graph<usize, E> + ?Sized>(graph: &G) -> Vec<usize> {
+      let adj = adjacency(graph);
+      let all = if adj.len() == 64 { !0 } else { (1u64 << adj.len()) - 1 };
+      let complement = adj.iter().enumerate().map(|(v, &a)| all & !a & !(1 << v) ).collect::<Vec<_>>();
+      let mut best = 0;
+      independent_set(&complement, all, 0, &mut best);
+      (0 .. adj.len()).filter(|&v| best >> v & 1 != 0 ).collect()
+    }
+
+    fn independent_set(adj: &[u64], rest: u64, current: u64, best: &mut u64) {
+      if current.count_ones() + rest.count_ones() <= best.count_ones() { return }
+      if rest == 0 {
+        *best = current;
+        return;
+      }
+      // a vertex of degree at most 1 can always be taken; otherwise branch on a vertex of maximum degree
+      let mut pivot = (0, 0);
+      let mut bits = rest;
+      while bits != 0 {
+        let v = bits.trailing_zeros() as usize;
+        bits &= bits - 1;
+        let degree = (adj[v] & rest).count_ones();
+        if degree <= 1 {
+          return independent_set(adj, rest & !adj[v] & !(1 << v), current | 1 << v, best);
+        }
+        if degree > pivot.0 { pivot = (degree, v) }
+      }
+      let v = pivot.1;
+      independent_set(adj, rest & !adj[v] & !(1 << v), current | 1 << v, best);
+      independent_set(adj, rest & !(1 << v), current, best);
+    }
+
+    /// shortest path visiting every vertex exactly once, by bit DP in `O(2^n n^2)` time and `O(2^n n)` memory (`n <= 20`)
+    /// returns the cost and the edge ids along the path
+    pub fn shortest_hamiltonian_path_by<C: Measure, E, G: Graph<usize, E> + ?Sized>(graph: &G, f: impl FnMut(&G::Edge) -> Option<C>) -> Option<(C, Vec<usize>)> {
+      hamiltonian(graph, false, f)
+    }
+
+    pub fn shortest_hamiltonian_path<E: Measure, G: Graph<usize, E> + ?Sized>(graph: &G) -> Option<(E, Vec<usize>)> {
+      hamiltonian(graph, false, |edge| Some(*edge.weight()) )
+    }
+
+    /// shortest cycle visiting every vertex exactly once (traveling salesman), by bit DP in `O(2^n n^2)` time and `O(2^n n)` memory (`n <= 20`)
+    /// returns the cost and the edge ids along the cycle from vertex `0`
+    pub fn shortest_hamiltonian_cycle_by<C: Measure, E, G: Graph<usize, E> + ?Sized>(graph: &G, f: impl FnMut(&G::Edge) -> Option<C>) -> Option<(C, Vec<usize>)> {
+      hamiltonian(graph, true, f)
+    }
+
+    pub fn shortest_hamiltonian_cycle<E: Measure, G: Graph<usize, E> + ?Sized>(graph: &G) -> Option<(E, Vec<usize>)> {
+      hamiltonian(graph, true, |edge| Some(*edge.weight()) )
+    }
+
+    fn hamiltonian<C: Measure, E, G: Graph<usize, E> + ?Sized>(graph: &G, cycle: bool, mut f: impl FnMut(&G::Edge) -> Option<C>) -> Option<(C, Vec<usize>)> {
+      let n = graph.n();
+      if n == 0 { return None }
+      assert!(n <= 20);
+      // the lightest arc `u -> v` for each pair
+      let mut best: Vec<Option<(C, usize)>> = vec![None; n * n];
+      for u in 0 .. n {
+        graph.each_edge_from(u, |e| {
+          let v = graph.edge(e).to();
+          if u == v { return }
+          if let Some(c) = (f)(graph.edge(e)) {
+            if best[u * n + v].map(|(x, _)| c < x ).unwrap_or(true) { best[u * n + v] = Some((c, e)) }
+          }
+        });
+      }
+      // dist[s * n + v]: shortest path through `s` ending at `v`, valid if `reach[s]` has `v`
+      let mut dist = vec![C::zero(); n << n];
+      let mut reach = vec![0u32; 1 << n];
+      for v in 0 .. n {
+        if !cycle || v == 0 { reach[1 << v] |= 1 << v }
+      }
+      for s in 1 .. 1usize << n {
+        let mut ends = reach[s];
+        while ends != 0 {
+          let u = ends.trailing_zeros() as usize;
+          ends &= ends - 1;
+          let d = dist[s * n + u];
+          for v in 0 .. n {
+            if s >> v & 1 != 0 { continue }
+            if let Some((c, _)) = best[u * n + v] {
+              let t = s | 1 << v;
+              if reach[t] >> v & 1 == 0 || d + c < dist[t * n + v] {
+                reach[t] |= 1 << v;
+                dist[t * n + v] = d + c;
+              }
+            }
+          }
+        }
+      }
+      let full = (1usize << n) - 1;
+      let (cost, last, mut v) = if cycle {
+        if n == 1 { return Some((C::zero(), vec![])) }
+        (1 .. n).filter(|&u| reach[full] >> u & 1 != 0 ).filter_map(|u| best[u * n].map(|(c, e)| (dist[full * n + u] + c, Some(e), u) ) ).min_by_key(|x| x.0 )?
+      } else {
+        (0 .. n).filter(|&v| reach[full] >> v & 1 != 0 ).map(|v| (dist[full * n + v], None, v) ).min_by_key(|x| x.0 )?
+      };
+      // the previous vertex is any `u` whose path extended by the arc `u -> v` attains `dist`
+      let mut edges = last.into_iter().collect::<Vec<_>>();
+      let mut s = full;
+      while s != 1 << v {
+        let t = s & !(1 << v);
+        let d = dist[s * n + v];
+        let (u, e) = (0 .. n).filter(|&u| reach[t] >> u & 1 != 0 ).find_map(|u| best[u * n + v].filter(|&(c, _)| dist[t * n + u] + c == d ).map(|(_, e)| (u, e) ) ).unwrap();
+        edges.push(e);
+        s = t;
+        v = u;
+      }
+      edges.reverse();
+      Some((cost, edges))
+    }
+  }
+
+  pub mod centroid {
+    use super::{Graph, Edge as _, measure::Measure};
+
+    /// centroid decomposition of a tree (or forest) on vertices `0 .. n`
+    #[derive(Debug, Clone)]
+    pub struct CentroidDecomposition {
+      adj: Vec<Vec<(usize, usize)>>,
+      parent: Vec<Option<usize>>,
+      depth: Vec<usize>,
+      order: Vec<usize>,
+    }
+    impl CentroidDecomposition {
+      pub fn new<E, G: Graph<usize, E> + ?Sized>(graph: &G) -> Self {
+        let n = graph.n();
+        let mut adj = vec![vec![]; n];
+        for (u, list) in adj.iter_mut().enumerate() { graph.each_edge_from(u, |e| list.push((e, graph.edge(e).to())) ) }
+        let mut parent = vec![None; n];
+        let mut depth = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut removed = vec![false; n];
+        let mut size = vec![0; n];
+        let mut up = vec![n; n];
+        for r in 0 .. n {
+          if removed[r] { continue }
+          let mut stack = vec![(r, None)];
+          while let Some((s, p)) = stack.pop() {
+            let mut component = vec![s];
+            up[s] = n;
+            let mut i = 0;
+            while i < component.len() {
+              let u = component[i];
+              for &(_, v) in &adj[u] {
+                if v != up[u] && !removed[v] {
+                  up[v] = u;
+                  component.push(v);
+                }
+              }
+              i += 1;
+            }
+            for &u in component.iter().rev() {
+              size[u] = 1 + adj[u].iter().filter(|&&(_, v)| up[v] == u && !removed[v] ).map(|&(_, v)| size[v] ).sum::<usize>();
+            }
+            let total = component.len();
+            let mut c = s;
+            while let Some(&(_, v)) = adj[c].iter().find(|&&(_, v)| up[v] == c && !removed[v] && size[v] * 2 > total ) { c = v }
+            removed[c] = true;
+            parent[c] = p;
+            depth[c] = p.map_or(0, |p| depth[p] + 1 );
+            order.push(c);
+            for &(_, v) in &adj[c] {
+              if !removed[v] { stack.push((v, Some(c))) }
+            }
+          }
+        }
+        Self { adj, parent, depth, order }
+      }
+
+      /// parent in the centroid tree
+      pub fn parent(&self, v: usize) -> Option<usize> { self.parent[v] }
+      /// depth in the centroid tree (`O(log n)`)
+      pub fn depth(&self, v: usize) -> usize { self.depth[v] }
+      /// centroids in the order they are removed (parents come first)
+      pub fn order(&self) -> &[usize] { &self.order }
+
+      /// subtrees around centroid `c` within its component, as `(vertex, distance from c)` in BFS order
+      pub fn subtrees(&self, c: usize) -> Vec<Vec<(usize, usize)>> { self.collect(c, |_| 1 ) }
+
+      /// same as `subtrees` but distances are measured by `f(edge)`
+      pub fn subtrees_by<E, G: Graph<usize, E> + ?Sized, C: Measure>(&self, graph: &G, c: usize, mut f: impl FnMut(&G::Edge) -> C) -> Vec<Vec<(usize, C)>> {
+        self.collect(c, |e| (f)(graph.edge(e)) )
+      }
+
+      /// iterate over `(centroid, subtrees(centroid))` for all centroids
+      /// total size is `O(n log n)`
+      pub fn iter(&self) -> impl Iterator<Item = (usize, Vec<Vec<(usize, usize)>>)> + '_ {
+        self.order.iter().map(move |&c| (c, self.subtrees(c)) )
+      }
+
+      fn collect<C: Measure>(&self, c: usize, mut f: impl FnMut(usize) -> C) -> Vec<Vec<(usize, C)>> {
+        let d = self.depth[c];
+        let mut subtrees = vec![];
+        for &(e, v) in &self.adj[c] {
+          if self.depth[v] <= d { continue }
+          let mut subtree = vec![(v, (f)(e))];
+          let mut from = vec![c];
+          let mut i = 0;
+          while i < subtree.len() {
+            let (u, du) = subtree[i];
+            for &(e, w) in &self.adj[u] {
+              if w == from[i] || self.depth[w] <= d { continue }
+              subtree.push((w, du + (f)(e)));
+              from.push(u);
+            }
+            i += 1;
+          }
+          subtrees.push(subtree);
+        }
+        subtrees
+      }
+    }
+  }
+
+  pub mod rerooting {
+    use super::{Graph, Edge as _};
+
+    /// operations of rerooting DP (全方位木DP)
+    pub trait RerootingHelper<E> {
+      /// value aggregated at a vertex
+      type S: Clone;
+      /// identity of `merge`
+      fn e(&self) -> Self::S;
+      /// commutative, associative merge of values from neighbors
+      fn merge(&self, x: &Self::S, y: &Self::S) -> Self::S;
+      /// value of the subtree rooted at `v` from the merged values of its children
+      fn add_root(&self, x: &Self::S, v: usize) -> Self::S;
+      /// value of the subtree rooted at `child` as seen from `parent` through an edge of `weight`
+      fn lift(&self, x: &Self::S, parent: usize, child: usize, weight: &E) -> Self::S;
+    }
+
+    /// DP value of the whole tree rooted at each vertex in `O(n)`
+    /// each undirected edge should be a pair of arcs; forests are handled per tree
+    pub fn rerooting<E, G: Graph<usize, E> + ?Sized, H: RerootingHelper<E>>(graph: &G, helper: &H) -> Vec<H::S> {
+      let n = graph.n();
+      let mut parent = vec![None; n];
+      let mut order = Vec::with_capacity(n);
+      let mut visited = vec![false; n];
+      for r in 0 .. n {
+        if visited[r] { continue }
+        visited[r] = true;
+        let start = order.len();
+        order.push(r);
+        for i in start .. {
+          let u = if let Some(&u) = order.get(i) { u } else { break };
+          graph.each_edge_from(u, |e| {
+            let v = graph.edge(e).to();
+            if visited[v] { return }
+            visited[v] = true;
+            parent[v] = Some((u, e));
+            order.push(v);
+          });
+        }
+      }
+      let mut children = vec![vec![]; n];
+      for &v in &order {
+        if let Some((p, e)) = parent[v] { children[p].push((v, e)) }
+      }
+      let mut down = vec![helper.e(); n];
+      for &u in order.iter().rev() {
+        let x = children[u].iter().fold(helper.e(), |x, &(c, e)| helper.merge(&x, &helper.lift(&down[c], u, c, graph.edge(e).weight())) );
+        down[u] = helper.add_root(&x, u);
+      }
+      let mut up = vec![helper.e(); n];
+      let mut result = vec![helper.e(); n];
+      for &u in &order {
+        let lifted = children[u].iter().map(|&(c, e)| helper.lift(&down[c], u, c, graph.edge(e).weight()) ).collect::<Vec<_>>();
+        let k = lifted.len();
+        let mut suffix = vec![helper.e(); k + 1];
+        for i in (0 .. k).rev() { suffix[i] = helper.merge(&lifted[i], &suffix[i + 1]) }
+        let mut prefix = up[u].clone();
+        for (i, &(c, e)) in children[u].iter().enumerate() {
+          let x = helper.add_root(&helper.merge(&prefix, &suffix[i + 1]), u);
+          up[c] = helper.lift(&x, c, u, graph.edge(e).weight());
+          prefix = helper.merge(&prefix, &lifted[i]);
+        }
+        result[u] = helper.add_root(&prefix, u);
+      }
+      result
+    }
+  }
+
+  impl<V: VertexId> Walker<V> {
+    pub fn new() -> Self { Self { visited: FxHashSet::default(), queue: VecDeque::new() } }
+    pub fn go_next(&mut self, v: V) -> bool { self.visited.insert(v) && { self.queue.push_back(v); true } }
+    pub fn go_later(&mut self, v: V) -> bool { self.visited.insert(v) && { self.queue.push_front(v); true } }
+    pub fn forget(&mut self, v: V) -> bool { self.visited.remove(&v) }
+    pub fn is_visited(&self, v: V) -> bool { self.visited.contains(&v) }
+  }
+  impl<V: VertexId> Iterator for Walker<V> {
+    type Item = V;
+    fn next(&mut self) -> Option<Self::Item> { self.queue.pop_back() }
+  }
+
+  impl<V, E> IntoEdge<V, E> for (V, V, E) {
+    fn into_edge(self) -> (V, V, E) { self }
+  }
+  impl<V, E: Default> IntoEdge<V, E> for (V, V) {
+    fn into_edge(self) -> (V, V, E) { (self.0, self.1, Default::default()) }
+  }
+  
+  pub mod measure {
+    use std::cmp::*;
+    use num_traits::*;
+    
     pub trait AssignOps: Sized + std::ops::AddAssign + std::ops::SubAssign + std::ops::MulAssign + std::ops::DivAssign + std::ops::RemAssign {}
     pub trait Measure: std::fmt::Debug + Num + Default + Ord + Copy + AssignOps + std::iter::Sum {
       fn if_chmin(&mut self, other: Self, f: impl FnOnce()) -> &mut Self { if *self > other { *self = other; (f)() }; self }
@@ -624,6 +3000,88 @@ pub mod graphs {
     }
   }
 
+  fn floyd_warshall_nested<V: VertexId, E, G: Graph<V, E> + ?Sized, C: Measure>(graph: &G, loops: bool, mut f: impl FnMut(&G::Edge) -> Option<C>) -> FxHashMap<V, FxHashMap<V, C>> {
+    let mut dist = FxHashMap::default();
+    for e in 0 .. graph.m() {
+      let edge = graph.edge(e);
+      if let Some(d) = (f)(edge) { dist.entry(edge.from()).or_insert_with(FxHashMap::default).chmin(edge.to(), d); }
+    }
+    if loops {
+      graph.each_vertex(|v| {
+        dist.entry(v).or_insert_with(FxHashMap::default).insert(v, C::zero());
+      });
+    }
+    graph.each_vertex(|k| {
+      graph.each_vertex(|i| {
+        graph.each_vertex(|j| {
+          if let Some((&d1, &d2)) = self::measure::zip(dist.get(&i).and_then(|d| d.get(&k) ), dist.get(&k).and_then(|d| d.get(&j) )) {
+            dist.entry(i).or_insert_with(FxHashMap::default).chmin(j, d1 + d2);
+          }
+        });
+      });
+    });
+    dist
+  }
+
+  /// vertices renumbered into `0 .. n` with adjacency lists of `(edge id, index of to)`
+  struct IndexedGraph<V> {
+    vertices: Vec<V>,
+    index: FxHashMap<V, usize>,
+    adj: Vec<Vec<(usize, usize)>>,
+  }
+  impl<V: VertexId> IndexedGraph<V> {
+    fn new<E, G: Graph<V, E> + ?Sized>(graph: &G) -> Self {
+      let mut vertices = vec![];
+      graph.each_vertex(|v| vertices.push(v) );
+      let mut index = vertices.iter().enumerate().map(|(i, &v)| (v, i) ).collect::<FxHashMap<_, _>>();
+      let mut adj = vec![];
+      let mut i = 0;
+      while i < vertices.len() {
+        let mut list = vec![];
+        graph.each_edge_from(vertices[i], |e| {
+          let to = graph.edge(e).to();
+          let j = *index.entry(to).or_insert_with(|| { vertices.push(to); vertices.len() - 1 });
+          list.push((e, j));
+        });
+        adj.push(list);
+        i += 1;
+      }
+      Self { vertices, index, adj }
+    }
+
+    fn len(&self) -> usize { self.vertices.len() }
+
+    /// adjacency lists where every arc `u -> v` also appears as `v -> u`
+    fn undirected(&self) -> Vec<Vec<(usize, usize)>> {
+      let mut adj = self.adj.clone();
+      for (u, list) in self.adj.iter().enumerate() {
+        for &(e, v) in list { adj[v].push((e, u)) }
+      }
+      adj
+    }
+
+    /// Kahn's algorithm; the order among available vertices is decided by the queue `Q`
+    fn topological_order<Q>(&self, init: impl FnOnce(&[usize]) -> Q, mut pop: impl FnMut(&mut Q) -> Option<usize>, mut push: impl FnMut(&mut Q, usize)) -> Option<Vec<usize>> {
+      let n = self.vertices.len();
+      let mut indeg = vec![0; n];
+      for list in &self.adj { for &(_, v) in list { indeg[v] += 1 } }
+      let mut queue = (init)(&indeg);
+      let mut order = Vec::with_capacity(n);
+      while let Some(u) = (pop)(&mut queue) {
+        order.push(u);
+        for &(_, v) in &self.adj[u] {
+          indeg[v] -= 1;
+          if indeg[v] == 0 { (push)(&mut queue, v) }
+        }
+      }
+      if order.len() == n { Some(order) } else { None }
+    }
+
+    fn topological_sort_indices(&self) -> Option<Vec<usize>> {
+      self.topological_order(|indeg| (0 .. indeg.len()).filter(|&u| indeg[u] == 0 ).collect::<Vec<_>>(), |stack| stack.pop(), |stack, v| stack.push(v) )
+    }
+  }
+
   struct Uniqueue<T> {
     queue: VecDeque<T>,
     inq: FxHashSet<T>,
@@ -652,6 +3110,9 @@ pub mod graphs {
   impl<C: Ord + Eq, V: PartialEq> Eq for DistV<C, V> {}
 
   pub trait Dic<K, V> {
+    /// keys are small integers, so `VecGraph` can use dense algorithms
+    const DENSE: bool = false;
+
     fn new() -> Self;
     fn insert(&mut self, key: K, value: V);
     fn get(&self, key: &K) -> Option<&V>;
@@ -676,6 +3137,8 @@ pub mod graphs {
   }
 
   impl<T: Default> Dic<usize, T> for Vec<Option<T>> {
+    const DENSE: bool = true;
+
     fn new() -> Self { Vec::new() }
     fn insert(&mut self, key: usize, value: T) {
       if key >= self.len() { self.resize_with(key + 1, || None) };
@@ -693,4 +3156,7 @@ pub mod graphs {
   use std::cmp::*;
   use itertools::*;
   use rustc_hash::*;
+  use num_traits::ToPrimitive;
+  use crate::unionfind::UnionFind;
+  use crate::leftist_heap::leftist_heap::{self as heap, LeftistHeap};
 }
@@ -0,0 +1,191 @@
+#![allow(clippy::module_inception)]
+pub mod leftist_heap {
+  use std::cmp::*;
+  use std::mem::*;
+
+  /// lazy operation applied to every key in a heap
+  /// it must preserve the order of keys
+  pub trait Action<T>: Clone {
+    fn act(&self, key: &mut T);
+    /// `self` followed by `other`
+    fn compose(&mut self, other: &Self);
+  }
+  impl<T> Action<T> for () {
+    fn act(&self, _: &mut T) {}
+    fn compose(&mut self, _: &Self) {}
+  }
+
+  /// adds a value to every key (`Reverse` keys make a min-heap)
+  #[derive(Debug, Clone, Copy)]
+  pub struct Add<C>(pub C);
+  impl<C: Copy + std::ops::AddAssign> Action<C> for Add<C> {
+    fn act(&self, key: &mut C) { *key += self.0 }
+    fn compose(&mut self, other: &Self) { self.0 += other.0 }
+  }
+  impl<C: Copy + std::ops::AddAssign, I> Action<(C, I)> for Add<C> {
+    fn act(&self, key: &mut (C, I)) { key.0 += self.0 }
+    fn compose(&mut self, other: &Self) { self.0 += other.0 }
+  }
+  impl<C: Copy + std::ops::AddAssign> Action<Reverse<C>> for Add<C> {
+    fn act(&self, key: &mut Reverse<C>) { key.0 += self.0 }
+    fn compose(&mut self, other: &Self) { self.0 += other.0 }
+  }
+  impl<C: Copy + std::ops::AddAssign, I> Action<Reverse<(C, I)>> for Add<C> {
+    fn act(&self, key: &mut Reverse<(C, I)>) { key.0 .0 += self.0 }
+    fn compose(&mut self, other: &Self) { self.0 += other.0 }
+  }
+
+  /// meldable max-heap
+  pub struct LeftistHeap<T: Ord, A: Action<T> = ()> {
+    root: Option<Box<LeftistHeapNode<T, A>>>,
+    len: usize,
+  }
+
+  impl<T: Ord, A: Action<T>> LeftistHeap<T, A> {
+    pub fn new() -> Self {
+      Self {
+        root: None,
+        len: 0,
+      }
+    }
+
+    pub fn push(&mut self, key: T) {
+      self.len += 1;
+      let node = LeftistHeapNode::new(key);
+      if let Some(root) = self.root.take() {
+        self.root = Some(root.meld(node));
+      } else {
+        self.root = Some(node);
+      }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+      let (left, right) = self.root.as_mut()?.split();
+      self.len -= 1;
+      let root = replace(&mut self.root, Self::meld(left, right));
+      root.map(|n| n.key)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+      self.root.as_ref().map(|node| &node.key)
+    }
+
+    /// applies `action` to every key
+    pub fn apply(&mut self, action: A) {
+      if let Some(root) = self.root.as_mut() {
+        root.apply(&action);
+      }
+    }
+
+    pub fn len(&self) -> usize {
+      self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+      self.len == 0
+    }
+
+    pub fn merge(&mut self, mut other: Self) {
+      let root = Self::meld(self.root.take(), other.root.take());
+      self.root = root;
+      self.len += other.len;
+    }
+
+    fn meld(left: Option<Box<LeftistHeapNode<T, A>>>, right: Option<Box<LeftistHeapNode<T, A>>>) -> Option<Box<LeftistHeapNode<T, A>>> {
+      if let Some(l) = left {
+        if let Some(r) = right {
+          Some(l.meld(r))
+        } else {
+          Some(l)
+        }
+      } else {
+        right
+      }
+    }
+  }
+
+  impl<T: Ord, A: Action<T>> Default for LeftistHeap<T, A> {
+    fn default() -> Self { Self::new() }
+  }
+
+  impl<T: Ord, A: Action<T>> std::iter::FromIterator<T> for LeftistHeap<T, A> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+      let mut heap = Self::new();
+      for x in iter {
+        heap.push(x);
+      }
+      heap
+    }
+  }
+
+  /// `key` is up to date; `lazy` is pending for the children
+  pub struct LeftistHeapNode<T: Ord, A: Action<T>> {
+    key: T,
+    lazy: Option<A>,
+    to_leaf: usize,
+    children: [Option<Box<LeftistHeapNode<T, A>>>; 2],
+  }
+
+  impl<T: Ord, A: Action<T>> LeftistHeapNode<T, A> {
+    pub fn new(key: T) -> Box<Self> {
+      Box::new(Self {
+        key,
+        lazy: None,
+        to_leaf: 0,
+        children: [None, None],
+      })
+    }
+
+    pub fn key(self) -> T {
+      self.key
+    }
+
+    pub fn apply(&mut self, action: &A) {
+      action.act(&mut self.key);
+      if let Some(lazy) = self.lazy.as_mut() {
+        lazy.compose(action);
+      } else {
+        self.lazy = Some(action.clone());
+      }
+    }
+
+    fn push(&mut self) {
+      if let Some(lazy) = self.lazy.take() {
+        for child in self.children.iter_mut().flatten() {
+          child.apply(&lazy);
+        }
+      }
+    }
+
+    pub fn split(self: &mut Box<Self>) -> (Option<Box<Self>>, Option<Box<Self>>) {
+      self.push();
+      let left = self.children[0].take();
+      let right = self.children[1].take();
+      (left, right)
+    }
+
+    pub fn meld(mut self: Box<Self>, mut other: Box<Self>) -> Box<Self> {
+      if self.key.cmp(&other.key) == Ordering::Less {
+        swap(&mut self, &mut other);
+      }
+      self.push();
+      if self.children[0].is_none() {
+        self.children[0] = Some(other);
+      } else {
+        if let Some(child) = self.children[1].take() {
+          other = child.meld(other);
+        }
+        self.children[1] = Some(other);
+        if Self::to_leaf(&self.children[0]) < Self::to_leaf(&self.children[1]) {
+          self.children.swap(0, 1);
+        }
+        self.to_leaf = Self::to_leaf(&self.children[1]) + 1;
+      }
+      self
+    }
+
+    fn to_leaf(node: &Option<Box<Self>>) -> usize {
+      node.as_ref().map(|node| node.to_leaf).unwrap_or(0)
+    }
+  }
+}
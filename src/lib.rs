@@ -4,4 +4,6 @@ pub mod matrix;
 pub mod modint;
 pub mod primes;
 pub mod treap;
-pub mod binary_trie;
\ No newline at end of file
+pub mod binary_trie;
+pub mod flow;
+pub mod leftist_heap;
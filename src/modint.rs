@@ -23,7 +23,7 @@ pub mod modint {
   macro_rules! impl_assign { ($t:ident, $f:ident, $r:ty, $b:expr) => { impl<N: Int> ops::$t<$r> for ModInt<N> { fn $f(&mut self, other: $r) { ($b)(self, other) } } } }
   macro_rules! impl_ops { ($t:ident, $f:ident, $g:ident, $r:ty) => { impl<N: Int> ops::$t<$r> for ModInt<N> { type Output = Self; fn $f(self, other: $r) -> Self { let mut r = self.clone(); (&mut r).$g(other); r } } } }
 
-  impl_assign!(AddAssign, add_assign, N, |x: &mut ModInt<N>, y: N| { x.value = x.value + y; if x.value > x.modulus { x.value = x.value - x.modulus; } });
+  impl_assign!(AddAssign, add_assign, N, |x: &mut ModInt<N>, y: N| { x.value = x.value + y; if x.value >= x.modulus { x.value = x.value - x.modulus; } });
   impl_assign!(SubAssign, sub_assign, N, |x: &mut ModInt<N>, y: N| { x.value = x.value - y; if x.value + x.modulus < x.modulus { x.value = x.value + x.modulus; } });
   impl_assign!(MulAssign, mul_assign, N, |x: &mut ModInt<N>, y: N| { x.value = (x.value * y).rem_euclid(x.modulus); });
   impl_assign!(DivAssign, div_assign, N, |x: &mut ModInt<N>, y: N| { *x *= ext_gcd(y, x.modulus).1; });
@@ -0,0 +1,126 @@
+use cp::flow::flow::*;
+
+#[test]
+fn max_flow_on_long_path() {
+  let n = 200_000;
+  let mut network = MaxFlow::new();
+  network.add_vertices(n);
+  for v in 0 .. n - 1 { network.add_arc(v, v + 1, 3); }
+  network.add_arc(0, n - 1, 2);
+  assert_eq!(network.flow(0, n - 1), 5);
+}
+
+#[test]
+fn min_cost_max_flow_with_unbounded_arcs() {
+  let mut network = MinCostFlow::new();
+  network.add_vertices(3);
+  for _ in 0 .. 10 { network.add_constrained_arc(0, 1, .., 1); }
+  network.add_arc(1, 2, 5, 1);
+  assert_eq!(network.max_flow(0, 2), (5, 10, Status::Optimal));
+}
+
+#[test]
+fn max_flow_with_huge_capacities() {
+  let mut network = MaxFlow::new();
+  network.add_vertices(3);
+  for _ in 0 .. 10 { network.add_arc(0, 1, i64::MAX / 4); }
+  network.add_arc(1, 2, 7);
+  assert_eq!(network.flow(0, 2), 7);
+}
+
+#[test]
+fn gomory_hu_tree_gives_all_pairs_min_cut() {
+  use cp::graphs::graphs::*;
+  let n = 6;
+  let edges = [(0, 1, 1), (0, 2, 7), (1, 2, 1), (1, 3, 3), (1, 4, 2), (2, 4, 4), (3, 4, 1), (3, 5, 6), (4, 5, 2)];
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for &(u, v, c) in &edges { graph.add_edge(u, v, c); }
+  let tree = gomory_hu_tree(&graph);
+  assert_eq!(tree.m(), 2 * (n - 1));
+  for u in 0 .. n {
+    // minimum weight on the tree path from `u`
+    let mut path_min = vec![None; n];
+    path_min[u] = Some(i64::MAX);
+    let mut stack = vec![u];
+    while let Some(x) = stack.pop() {
+      for e in tree.edges_from(x) {
+        let edge = tree.edge(e);
+        if path_min[edge.to()].is_none() {
+          path_min[edge.to()] = Some(path_min[x].unwrap().min(*edge.weight()));
+          stack.push(edge.to());
+        }
+      }
+    }
+    for (v, &expected) in path_min.iter().enumerate() {
+      if u == v { continue }
+      let (mut network, _) = MaxFlow::from_graph(&graph);
+      assert_eq!(expected, Some(network.flow(u, v)));
+    }
+  }
+}
+
+#[test]
+fn max_flow_cut_and_decomposition() {
+  use cp::graphs::graphs::*;
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for &(u, v, c) in &[(0, 1, 3), (0, 2, 2), (1, 2, 1), (1, 3, 2), (2, 3, 4)] { graph.add_arc(u, v, c); }
+  let (mut network, index) = MaxFlow::from_graph(&graph);
+  assert_eq!(index[&3], 3);
+  let mut limited = network.clone();
+  assert_eq!(limited.flow_limited(0, 3, 2), 2);
+  assert_eq!(network.flow(0, 3), 5);
+  assert_eq!(network.cut_vertices(0), vec![0]);
+  assert_eq!(network.cut_arcs(0), vec![0, 2]);
+  let paths = network.decompose(0, 3);
+  assert_eq!(paths.iter().map(|&(f, _)| f ).sum::<i64>(), 5);
+  for (_, path) in &paths {
+    assert_eq!((network.arc(path[0]).from, network.arc(*path.last().unwrap()).to), (0, 3));
+  }
+  // edge `e` of the graph is arc `2 * e`, and the cut arcs are saturated
+  assert!([0, 1].iter().all(|&e| network.residual_cap(2 * e) == 0 ));
+}
+
+#[test]
+fn min_cost_b_flow_with_potentials() {
+  let mut network = MinCostFlow::new();
+  network.add_vertices(4);
+  for &(u, v, cap, cost) in &[(0, 1, 2, 1), (0, 2, 2, 2), (1, 3, 2, 1), (2, 3, 2, 1), (1, 2, 1, 0)] { network.add_arc(u, v, cap, cost); }
+  network.add_supply(0, 3);
+  network.add_demand(3, 3);
+  assert_eq!(network.solve(), (7, Status::Optimal));
+  assert_eq!(network.excess(3), 0);
+  let p = network.potential();
+  for e in 0 .. network.m() {
+    let arc = network.arc(e);
+    if arc.residual_cap() > 0 { assert!(arc.cost + p[arc.from] - p[arc.to] >= 0) }
+  }
+}
+
+#[test]
+fn min_cost_flow_lower_bounds() {
+  // one unit must take the cycle 0-1-2 of cost 3, leaving 3 units of capacity for the cycle 0-1 of cost -1
+  let mut network = MinCostFlow::new();
+  network.add_vertices(3);
+  network.add_arc(0, 1, 4, -2);
+  network.add_arc(1, 0, 4, 1);
+  network.add_constrained_arc(1, 2, 1 ..= 3, 5);
+  network.add_arc(2, 0, 3, 0);
+  assert_eq!(network.solve(), (0, Status::Optimal));
+  assert_eq!((network.arc(0).flow, network.arc(4).flow), (4, 1));
+
+  // nothing leaves vertex 1, so the lower bound cannot be met
+  let mut network = MinCostFlow::new();
+  network.add_vertices(2);
+  network.add_constrained_arc(0, 1, 2 ..= 3, 1);
+  assert_eq!(network.solve().1, Status::Infeasible);
+  assert_eq!(network.max_flow(0, 1).2, Status::Infeasible);
+}
+
+#[test]
+fn min_cost_flow_slope() {
+  let mut network = MinCostFlow::new();
+  network.add_vertices(2);
+  network.add_arc(0, 1, 1, 1);
+  network.add_arc(0, 1, 2, 3);
+  assert_eq!(network.slope(0, 1, 10), (vec![(0, 0), (1, 1), (3, 7)], Status::Optimal));
+}
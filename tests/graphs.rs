@@ -0,0 +1,481 @@
+use cp::graphs::graphs::*;
+
+#[test]
+fn bellman_ford_unbounded_follows_enabled_edges() {
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for v in 0 .. 4 { graph.vertex_mut(v); }
+  graph.add_arc(0, 1, -1);
+  graph.add_arc(1, 2, -1);
+  graph.add_arc(2, 1, -1);
+  graph.add_arc(2, 3, 1);
+  let result = graph.shortest_path_tree_bellman_ford_by(0, |edge, d: i64| if (edge.from(), edge.to()) == (2, 3) { None } else { Some(d + *edge.weight()) } );
+  let cycle = result.unwrap_err();
+  let mut unbounded = cycle.unbounded().iter().copied().collect::<Vec<_>>();
+  unbounded.sort();
+  assert_eq!(unbounded, vec![1, 2]);
+}
+
+#[test]
+fn johnson_exposes_feasible_potentials() {
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for v in 0 .. 4 { graph.vertex_mut(v); }
+  graph.add_arc(0, 1, 2);
+  graph.add_arc(1, 2, -3);
+  graph.add_arc(2, 3, 1);
+  graph.add_arc(3, 0, 1);
+  graph.add_arc(0, 2, -1);
+  let dist = graph.shortest_paths_johnson().unwrap();
+  for e in 0 .. graph.m() {
+    let edge = graph.edge(e);
+    assert!(dist.potential(edge.from()).unwrap() + *edge.weight() >= dist.potential(edge.to()).unwrap());
+  }
+  assert_eq!(dist.get(0, 3), Some(0));
+  assert_eq!(graph.shortest_paths_floyd_warshall_dense().unwrap().potential(0), None);
+}
+
+#[test]
+fn lca_on_unweighted_tree() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for &(u, v) in &[(0, 1), (0, 2), (1, 3), (1, 4)] { graph.add_edge(u, v, ()); }
+  let lca = LCA::new_unweighted(&graph, 0);
+  assert_eq!(lca.lca(3, 4), 1);
+  assert_eq!(lca.lca(3, 2), 0);
+  assert_eq!(lca.dist(3, 2), 3);
+  assert_eq!(lca.parent(4), Some(1));
+
+  // a forest of the trees rooted at 0 and 5
+  graph.add_edge(5, 6, ());
+  graph.add_edge(6, 7, ());
+  let lca = LCA::new_unweighted_multiple(&graph, &[0, 5]);
+  assert_eq!(lca.lca(7, 5), 5);
+  assert_eq!(lca.dist(3, 4), 2);
+  assert_eq!((lca.root(4), lca.root(7)), (0, 5));
+  assert!(std::panic::catch_unwind(|| lca.lca(3, 7) ).is_err());
+}
+
+#[test]
+fn minimum_arborescence_with_unsigned_weights() {
+  let mut graph: VecGraph<usize> = VecGraph::new();
+  for v in 0 .. 4 { graph.vertex_mut(v); }
+  graph.add_arc(0, 1, 10);
+  graph.add_arc(0, 2, 12);
+  graph.add_arc(1, 2, 1);
+  graph.add_arc(2, 1, 1);
+  graph.add_arc(2, 3, 5);
+  graph.add_arc(3, 1, 2);
+  let (cost, edges) = graph.minimum_arborescence(0).unwrap();
+  assert_eq!(cost, 16);
+  assert_eq!(edges.iter().map(|&e| *graph.edge(e).weight() ).sum::<usize>(), 16);
+  assert_eq!(graph.minimum_arborescence(3), None);
+}
+
+#[test]
+fn chromatic_number_of_odd_cycle() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for v in 0 .. 5 { graph.add_edge(v, (v + 1) % 5, ()); }
+  assert_eq!(chromatic_number(&graph), 3);
+}
+
+fn assert_eulerian_trail<E>(graph: &VecGraph<E>, trail: &[usize], edges: usize) {
+  // arcs `2i` and `2i + 1` come from the same `add_edge`
+  let mut used = vec![false; edges];
+  for &e in trail {
+    assert!(!used[e / 2]);
+    used[e / 2] = true;
+  }
+  assert!(used.iter().all(|&x| x));
+  for w in trail.windows(2) {
+    assert_eq!(graph.edge(w[0]).to(), graph.edge(w[1]).from());
+  }
+}
+
+#[test]
+fn eulerian_trail_pairs_self_loops_by_add_edge() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  graph.add_edge(0, 0, ());
+  graph.add_edge(0, 0, ());
+  let trail = graph.eulerian_trail(false).unwrap();
+  assert_eulerian_trail(&graph, &trail, 2);
+}
+
+#[test]
+fn eulerian_trail_uses_every_edge_once() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  let edges = [(0, 1), (1, 2), (2, 0), (0, 1), (1, 1), (2, 3), (3, 3), (3, 2), (1, 1), (0, 2)];
+  for &(u, v) in &edges { graph.add_edge(u, v, ()); }
+  let trail = graph.eulerian_trail(false).unwrap();
+  assert_eulerian_trail(&graph, &trail, edges.len());
+  // vertices 1 and 2 have odd degree
+  assert_eq!(graph.edge(trail[0]).from() + graph.edge(*trail.last().unwrap()).to(), 3);
+}
+
+#[test]
+fn floyd_warshall_keeps_lightest_parallel_edge() {
+  let mut vec_graph: VecGraph<i64> = VecGraph::new();
+  let mut hash_graph: HashGraph<usize, i64> = HashGraph::new();
+  for &(u, v, c) in &[(0, 1, 2), (0, 1, 7), (1, 2, -1), (2, 0, 4)] {
+    vec_graph.add_arc(u, v, c);
+    hash_graph.add_arc(u, v, c);
+  }
+  for dist in [vec_graph.shortest_paths_floyd_warshall(), hash_graph.shortest_paths_floyd_warshall()] {
+    assert_eq!(dist[&0][&1], 2);
+    assert_eq!(dist[&0][&2], 1);
+    assert_eq!(dist[&2][&1], 6);
+    assert_eq!(dist[&1][&1], 0);
+  }
+  let dense = vec_graph.shortest_paths_floyd_warshall_dense().unwrap();
+  assert_eq!(dense.get(2, 1), Some(6));
+  assert_eq!(dense.path(2, 1), Some(vec![3, 0]));
+}
+
+#[test]
+fn shortest_hamiltonian_path_and_cycle() {
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for &(u, v, c) in &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 10), (0, 2, 2), (2, 1, 2), (1, 3, 2), (3, 0, 3)] { graph.add_arc(u, v, c); }
+  let (cost, edges) = shortest_hamiltonian_path(&graph).unwrap();
+  assert_eq!((cost, edges), (3, vec![0, 1, 2]));
+  let (cost, edges) = shortest_hamiltonian_cycle(&graph).unwrap();
+  assert_eq!((cost, edges), (6, vec![0, 1, 2, 7]));
+}
+
+#[test]
+fn spfa_counts_unregistered_targets_as_vertices() {
+  // only `from` vertices are registered by `add_arc`
+  let mut graph: HashGraph<usize, i64> = HashGraph::new();
+  graph.add_arc(0, 1, -2);
+  graph.add_arc(0, 2, 3);
+  graph.add_arc(1, 2, -4);
+  graph.add_arc(1, 3, 1);
+  let tree = graph.shortest_path_tree_spfa(0).unwrap();
+  assert_eq!((tree.distance(2), tree.distance(3)), (Some(-6), Some(-1)));
+  assert_eq!(tree.path_to(2), vec![0, 2]);
+}
+
+#[test]
+fn strongly_connected_components_in_topological_order() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for v in 0 .. 6 { graph.vertex_mut(v); }
+  for &(u, v) in &[(3, 4), (4, 3), (0, 1), (1, 2), (2, 0), (2, 3), (1, 4), (5, 0)] { graph.add_arc(u, v, ()); }
+  let (id, dag) = graph.strongly_connected_components();
+  assert_eq!(dag.n(), 3);
+  assert_eq!((id[&0], id[&1], id[&2]), (1, 1, 1));
+  assert_eq!((id[&3], id[&4], id[&5]), (2, 2, 0));
+  // parallel arcs between components are merged
+  assert_eq!(dag.m(), 2);
+  assert_eq!(dag.adjacent_vertices(0), vec![1]);
+  assert_eq!(dag.adjacent_vertices(1), vec![2]);
+
+  let mut graph: HashGraph<char, ()> = HashGraph::new();
+  for &(u, v) in &[('a', 'b'), ('b', 'a'), ('b', 'c')] { graph.add_arc(u, v, ()); }
+  let (id, dag) = graph.strongly_connected_components();
+  assert_eq!(id[&'a'], id[&'b']);
+  assert!(id[&'a'] < id[&'c']);
+  assert_eq!(dag.m(), 1);
+}
+
+#[test]
+fn strongly_connected_components_on_long_cycle() {
+  let n = 200_000;
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for v in 0 .. n { graph.add_arc(v, (v + 1) % n, ()); }
+  let (id, dag) = graph.strongly_connected_components();
+  assert_eq!(dag.n(), 1);
+  assert!(id.values().all(|&c| c == 0 ));
+}
+
+#[test]
+fn two_sat_satisfiable_and_unsatisfiable() {
+  let mut sat = TwoSat::new(3);
+  sat.fix(2, true);
+  sat.add_equality(1, false, 2, true);
+  sat.add_clause(0, true, 1, true);
+  sat.add_implication(0, true, 2, true);
+  sat.add_at_most_one(&[(0, true), (1, true)]);
+  let x = sat.satisfiable().unwrap();
+  assert_eq!(x[.. 3], [true, false, true]);
+  sat.add_at_most_one(&[(0, true), (2, true)]);
+  assert_eq!(sat.satisfiable(), None);
+
+  let mut sat = TwoSat::new(1);
+  sat.add_clause(0, true, 0, true);
+  sat.add_clause(0, false, 0, false);
+  assert_eq!(sat.satisfiable(), None);
+}
+
+#[test]
+fn lowlink_bridges_and_components() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (5, 6), (6, 7), (6, 7)];
+  for &(u, v) in &edges { graph.add_edge(u, v, ()); }
+  let lowlink = graph.lowlink();
+  // the double edge 6-7 is not a bridge
+  let mut bridges = lowlink.bridges().into_iter().map(|e| { let edge = graph.edge(e); (edge.from().min(edge.to()), edge.from().max(edge.to())) } ).collect::<Vec<_>>();
+  bridges.sort();
+  assert_eq!(bridges, vec![(2, 3), (5, 6)]);
+  let mut articulation = lowlink.articulation_points().to_vec();
+  articulation.sort();
+  assert_eq!(articulation, vec![2, 3, 5, 6]);
+
+  let (id, tree) = lowlink.two_edge_connected_components();
+  assert_eq!(tree.n(), 3);
+  assert_eq!(tree.m(), 4);
+  assert!(id[&0] == id[&1] && id[&1] == id[&2]);
+  assert!(id[&3] == id[&4] && id[&4] == id[&5]);
+  assert_eq!(id[&6], id[&7]);
+  assert!(id[&0] != id[&3] && id[&3] != id[&6]);
+
+  let mut blocks = lowlink.biconnected_components().into_iter().map(|mut b| { b.sort(); b } ).collect::<Vec<_>>();
+  blocks.sort();
+  assert_eq!(blocks, vec![vec![0, 1, 2], vec![2, 3], vec![3, 4, 5], vec![5, 6], vec![6, 7]]);
+  assert_eq!(lowlink.biconnected_component_edges().iter().map(|es| es.len() ).sum::<usize>(), edges.len());
+  let block_cut = lowlink.block_cut_tree();
+  assert_eq!(block_cut.n(), 8 + 5);
+  assert_eq!(block_cut.m(), 2 * 12);
+}
+
+#[test]
+fn minimum_spanning_tree_on_disconnected_graph() {
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for v in 0 .. 6 { graph.vertex_mut(v); }
+  for &(u, v, c) in &[(0, 1, 4), (1, 2, 1), (0, 2, 2), (3, 4, 5), (3, 4, 3), (2, 2, 0)] { graph.add_edge(u, v, c); }
+  let (cost, tree) = graph.minimum_spanning_tree_prim(0);
+  assert_eq!(cost, 3);
+  assert_eq!((tree.n(), tree.m()), (3, 4));
+  for (cost, forest) in [graph.minimum_spanning_tree_kruskal(), graph.minimum_spanning_tree_boruvka()] {
+    assert_eq!(cost, 6);
+    // two tree edges and one forest edge, each as a pair of arcs
+    assert_eq!(forest.m(), 6);
+    assert_eq!(forest.connected_components().len(), 3);
+  }
+}
+
+#[test]
+fn shortest_path_trees_reconstruct_paths() {
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for v in 0 .. 5 { graph.vertex_mut(v); }
+  for &(u, v, c) in &[(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)] { graph.add_arc(u, v, c); }
+  let dijkstra = graph.shortest_path_tree_dijkstra(0);
+  let spfa = graph.shortest_path_tree_spfa(0).unwrap();
+  for tree in &[dijkstra, spfa] {
+    assert_eq!(tree.distance(3), Some(4));
+    assert_eq!(tree.path_to(3), vec![1, 2, 3]);
+    assert_eq!(tree.prev_edge(1), Some(2));
+    assert_eq!(tree.path_to(0), vec![]);
+    assert_eq!((tree.distance(4), tree.path_to(4)), (None, vec![]));
+  }
+  let bfs = graph.shortest_path_tree_bfs::<usize>(0);
+  assert_eq!(bfs.distance(3), Some(2));
+  assert_eq!(bfs.path_to(3).len(), 2);
+  let path = bfs.path_to(3);
+  assert_eq!((graph.edge(path[0]).from(), graph.edge(path[1]).to()), (0, 3));
+}
+
+#[test]
+fn zero_one_bfs_and_dial_match_dijkstra() {
+  let mut graph: VecGraph<usize> = VecGraph::new();
+  for v in 0 .. 6 { graph.vertex_mut(v); }
+  for &(u, v, c) in &[(0, 1, 1), (0, 2, 0), (2, 1, 0), (1, 3, 1), (2, 4, 1), (4, 3, 0), (3, 0, 0)] { graph.add_arc(u, v, c); }
+  let dist = graph.shortest_path_01bfs(0);
+  assert_eq!(dist, graph.shortest_path_dijkstra(0));
+  assert_eq!((dist[&1], dist[&3], dist[&4]), (0, 1, 1));
+  assert!(!dist.contains_key(&5));
+  assert_eq!(graph.shortest_path_tree_01bfs_by(0, |edge, d: usize| Some(d + *edge.weight()) ).path_to(4), vec![1, 4]);
+
+  let mut graph: VecGraph<usize> = VecGraph::new();
+  for &(u, v, c) in &[(0, 1, 3), (0, 2, 1), (2, 1, 1), (1, 3, 2), (2, 3, 3), (3, 4, 0)] { graph.add_arc(u, v, c); }
+  let dist = graph.shortest_path_dial(0, 3);
+  assert_eq!(dist, graph.shortest_path_dijkstra(0));
+  assert_eq!((dist[&1], dist[&3], dist[&4]), (2, 4, 4));
+}
+
+#[test]
+fn topological_sort_and_dag_dp() {
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for v in 0 .. 4 { graph.vertex_mut(v); }
+  for &(u, v, c) in &[(3, 1, 5), (2, 1, 1), (1, 0, 2), (2, 0, 3)] { graph.add_arc(u, v, c); }
+  let order = graph.topological_sort().unwrap();
+  let pos = |v: usize| order.iter().position(|&x| x == v ).unwrap();
+  for e in 0 .. graph.m() { assert!(pos(graph.edge(e).from()) < pos(graph.edge(e).to())); }
+  assert_eq!(graph.topological_sort_min(), Some(vec![2, 3, 1, 0]));
+  let longest = graph.longest_path_dag().unwrap();
+  assert_eq!((longest[&0], longest[&1], longest[&2]), (7, 5, 0));
+  let shortest = graph.count_shortest_paths_dag_by(2, 1usize, |edge| Some(*edge.weight()) ).unwrap();
+  assert_eq!((shortest[&0], shortest[&1]), ((3, 2), (1, 1)));
+  assert!(!shortest.contains_key(&3));
+  graph.add_arc(0, 3, 1);
+  assert_eq!(graph.topological_sort(), None);
+  assert_eq!(graph.topological_sort_min(), None);
+  assert_eq!(graph.longest_path_dag(), None);
+}
+
+#[test]
+fn count_paths_dag_modulo() {
+  use cp::modint::modint::ModInt;
+  // ten diamonds in a row give 2^10 paths
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for i in 0 .. 10 {
+    for &(u, v) in &[(0, 1), (0, 2), (1, 3), (2, 3)] { graph.add_arc(3 * i + u, 3 * i + v, ()); }
+  }
+  let count = graph.count_paths_dag(0, ModInt::with_mod(1, 1000)).unwrap();
+  assert_eq!(count[&30].value(), 24);
+  assert_eq!(count[&15].value(), 32);
+  assert_eq!(count[&16].value(), 32);
+}
+
+#[test]
+fn bipartite_matching_with_konig() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for &(u, v) in &[(0, 3), (0, 4), (1, 3), (2, 3), (5, 2)] { graph.add_arc(u, v, ()); }
+  let color = graph.is_bipartite().unwrap();
+  assert!((0 .. 3).all(|v| color[&v] == color[&0] ) && (3 .. 6).all(|v| color[&v] != color[&0] ));
+  let matching = graph.bipartite_matching().unwrap();
+  assert_eq!(matching.size(), 3);
+  assert_eq!(matching.edges().len(), 3);
+  assert_eq!((matching.mate(0), matching.mate(4)), (Some(4), Some(0)));
+  let mut cover = matching.minimum_vertex_cover();
+  cover.sort();
+  assert_eq!(cover.len(), 3);
+  for e in 0 .. graph.m() {
+    let edge = graph.edge(e);
+    assert!(cover.contains(&edge.from()) || cover.contains(&edge.to()));
+  }
+  assert_eq!(matching.maximum_independent_set().len(), 3);
+
+  graph.add_arc(1, 2, ());
+  let cycle = graph.is_bipartite().unwrap_err();
+  assert_eq!(cycle.len() % 2, 1);
+  assert!(graph.bipartite_matching().is_err());
+}
+
+#[test]
+fn general_matching_through_blossom() {
+  // an odd cycle 0-1-2-3-4 with a pendant 3-5 and a path 1-7-6 has a perfect matching
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for &(u, v) in &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (6, 7), (3, 5), (7, 1)] { graph.add_arc(u, v, ()); }
+  let matching = graph.maximum_matching();
+  assert_eq!(matching.len(), 4);
+  let mut used = [false; 8];
+  for &e in &matching {
+    let edge = graph.edge(e);
+    assert!(!used[edge.from()] && !used[edge.to()]);
+    used[edge.from()] = true;
+    used[edge.to()] = true;
+  }
+  assert_eq!(graph.maximum_matching_size_tutte(), 4);
+  graph.add_arc(5, 6, ());
+  graph.add_arc(5, 8, ());
+  graph.add_arc(8, 9, ());
+  assert_eq!(graph.maximum_matching().len(), 5);
+  assert_eq!(graph.maximum_matching_size_tutte(), 5);
+}
+
+#[test]
+fn heavy_light_decomposition_paths() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for &(u, v) in &[(0, 1), (0, 2), (1, 3), (1, 4), (4, 5), (2, 6)] { graph.add_edge(u, v, ()); }
+  let hld = HeavyLightDecomposition::new(&graph, 0);
+  assert_eq!((hld.lca(5, 3), hld.lca(5, 6), hld.dist(5, 6)), (1, 0, 5));
+  assert_eq!((hld.parent(5), hld.depth(5)), (Some(4), 3));
+  assert_eq!((hld.la(5, 2), hld.la(5, 4)), (Some(1), None));
+  assert_eq!((hld.jump(5, 6, 3), hld.jump(5, 6, 5), hld.jump(5, 6, 6)), (Some(0), Some(6), None));
+  let mut subtree = hld.subtree(1).map(|i| hld.vertex(i) ).collect::<Vec<_>>();
+  subtree.sort();
+  assert_eq!(subtree, vec![1, 3, 4, 5]);
+  let walk = |ranges: Vec<(std::ops::Range<usize>, bool)>| ranges.into_iter().flat_map(|(range, down)| if down { range.rev().collect::<Vec<_>>() } else { range.collect() } ).map(|i| hld.vertex(i) ).collect::<Vec<_>>();
+  assert_eq!(walk(hld.path_vertices(5, 6)), vec![5, 4, 1, 0, 2, 6]);
+  assert_eq!(walk(hld.path_vertices(6, 3)), vec![6, 2, 0, 1, 3]);
+  // edge `(parent(x), x)` is stored at `pos(x)`
+  assert_eq!(walk(hld.path_edges(5, 6)), vec![5, 4, 1, 2, 6]);
+}
+
+#[test]
+fn centroid_decomposition_of_path() {
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for v in 0 .. 6 { graph.add_edge(v, v + 1, 10); }
+  let centroids = CentroidDecomposition::new(&graph);
+  assert_eq!(centroids.order()[0], 3);
+  assert_eq!((centroids.parent(3), centroids.parent(1), centroids.parent(5)), (None, Some(3), Some(3)));
+  assert_eq!((0 .. 7).map(|v| centroids.depth(v) ).collect::<Vec<_>>(), vec![2, 1, 2, 0, 2, 1, 2]);
+  let mut subtrees = centroids.subtrees(3);
+  subtrees.sort();
+  assert_eq!(subtrees, vec![vec![(2, 1), (1, 2), (0, 3)], vec![(4, 1), (5, 2), (6, 3)]]);
+  let mut weighted = centroids.subtrees_by(&graph, 5, |edge| *edge.weight() );
+  weighted.sort();
+  assert_eq!(weighted, vec![vec![(4, 10)], vec![(6, 10)]]);
+  // every vertex appears in the subtrees of its centroid ancestors only
+  let total = centroids.iter().map(|(_, subtrees)| subtrees.iter().map(|s| s.len() ).sum::<usize>() ).sum::<usize>();
+  assert_eq!(total, (0 .. 7).map(|v| centroids.depth(v) ).sum::<usize>());
+}
+
+struct Eccentricity;
+impl RerootingHelper<i64> for Eccentricity {
+  type S = i64;
+  fn e(&self) -> i64 { 0 }
+  fn merge(&self, x: &i64, y: &i64) -> i64 { *x.max(y) }
+  fn add_root(&self, x: &i64, _: usize) -> i64 { *x }
+  fn lift(&self, x: &i64, _: usize, _: usize, weight: &i64) -> i64 { x + weight }
+}
+
+#[test]
+fn rerooting_eccentricity() {
+  let mut graph: VecGraph<i64> = VecGraph::new();
+  for &(u, v, c) in &[(0, 1, 2), (1, 2, 3), (1, 3, 4), (0, 4, 1), (5, 6, 1)] { graph.add_edge(u, v, c); }
+  assert_eq!(rerooting(&graph, &Eccentricity), vec![6, 4, 7, 7, 7, 1, 1]);
+}
+
+#[test]
+fn euler_tour_subtrees_and_lca() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for &(u, v) in &[(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)] { graph.add_edge(u, v, ()); }
+  let tour = EulerTour::new(&graph, 0);
+  assert_eq!(tour.tour().len(), 11);
+  assert_eq!(tour.depth_sequence().len(), 11);
+  assert_eq!(tour.tour()[tour.first(4)], 4);
+  let mut subtree = tour.subtree(1).map(|i| tour.order()[i] ).collect::<Vec<_>>();
+  subtree.sort();
+  assert_eq!(subtree, vec![1, 3, 4]);
+  assert_eq!(tour.tout(1) - tour.tin(1), 3);
+  assert!(tour.is_ancestor(0, 4) && tour.is_ancestor(1, 4) && !tour.is_ancestor(2, 4));
+  assert_eq!((tour.lca(3, 4), tour.lca(4, 5), tour.lca(2, 5)), (1, 0, 2));
+  assert_eq!((tour.dist(3, 5), tour.depth(5)), (4, 2));
+
+  // the streaming version passes each tree edge down and back up
+  let mut walk = vec![];
+  graph.eulertour(0, |edge| walk.push((edge.from(), edge.to())) );
+  assert_eq!(walk.len(), 10);
+  assert_eq!(walk[0].0, 0);
+  assert_eq!(walk.last().unwrap().1, 0);
+  assert!(walk.windows(2).all(|w| w[0].1 == w[1].0 ));
+}
+
+#[test]
+fn functional_graph_jumps_and_cycles() {
+  let graph = FunctionalGraph::new(vec![1, 2, 0, 2, 3, 6, 5, 5]);
+  assert_eq!(graph.cycles().len(), 2);
+  assert_eq!(graph.cycle(graph.cycle_id(4)).len(), 3);
+  assert_eq!(graph.cycle_id(7), graph.cycle_id(6));
+  assert!(graph.is_on_cycle(0) && !graph.is_on_cycle(3));
+  assert_eq!((graph.cycle_entry(4), graph.dist_to_cycle(4), graph.dist_to_cycle(1)), (2, 2, 0));
+  assert_eq!((graph.jump(4, 0), graph.jump(4, 3), graph.jump(4, 1_000_000_000_000_000_000)), (4, 0, 1));
+  assert_eq!((graph.first_reach(4, 0), graph.first_reach(2, 1), graph.first_reach(0, 4), graph.first_reach(7, 0)), (Some(3), Some(2), None, None));
+
+  let mut namori: VecGraph<()> = VecGraph::new();
+  for &(u, v) in &[(0, 1), (1, 2), (2, 0), (3, 2), (4, 3)] { namori.add_edge(u, v, ()); }
+  let graph = FunctionalGraph::from_namori(&namori);
+  assert_eq!(graph.cycles().len(), 1);
+  assert_eq!((graph.next(4), graph.next(3)), (3, 2));
+  assert_eq!(graph.jump(0, 3), 0);
+}
+
+#[test]
+fn dominator_tree_with_unreachable_vertices() {
+  let mut graph: VecGraph<()> = VecGraph::new();
+  for v in 0 .. 7 { graph.vertex_mut(v); }
+  for &(u, v) in &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 1), (5, 3), (2, 6)] { graph.add_arc(u, v, ()); }
+  let tree = DominatorTree::new(&graph, 0);
+  assert_eq!(tree.root(), 0);
+  assert_eq!(tree.idoms(), &[None, Some(0), Some(0), Some(0), Some(3), None, Some(2)]);
+  assert!(!tree.is_reachable(5) && tree.is_reachable(6));
+  assert!(tree.dominates(3, 4) && tree.dominates(0, 4) && tree.dominates(4, 4));
+  assert!(!tree.dominates(1, 4) && !tree.dominates(5, 3) && !tree.dominates(0, 5));
+}